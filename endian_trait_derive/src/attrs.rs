@@ -0,0 +1,380 @@
+/*! Parsing for `#[endian(…)]` helper attributes
+
+The derive accepts a comma-separated list of options inside each `#[endian]`
+attribute. Options are either bare words, such as `no_padding`, or `key = value`
+pairs, such as `size = 16`. Options may be split across as many `#[endian]`
+attributes as the user likes, but each option may only be given once.
+!*/
+
+use pm2::Span;
+
+use syn::{
+	Attribute,
+	Expr,
+	ExprLit,
+	Ident,
+	LitInt,
+	LitStr,
+	Path,
+	Token,
+	Type,
+	ext::IdentExt,
+	parse::ParseStream,
+};
+
+/// Options placed on the type being derived, rather than on its fields.
+#[derive(Default)]
+pub struct Container {
+	/// `#[endian(no_padding)]`: the type must be exactly as wide as the sum of
+	/// its fields.
+	pub no_padding: Option<Span>,
+	/// `#[endian(from_bytes)]`: as `no_padding`, and every bit pattern must be a
+	/// valid value of the type.
+	pub from_bytes: Option<Span>,
+	/// `#[endian(size = N)]`: the type must be exactly `N` bytes wide.
+	pub size: Option<LitInt>,
+	/// `#[endian(bit_order = "msb" | "lsb")]`: where the first bit field of a
+	/// group is placed in its integer.
+	pub bit_order: Option<BitOrder>,
+	/// `#[endian(wire)]`: `#[derive(Endian)]` also implements `Wire`.
+	pub wire: Option<Span>,
+	/// `#[endian(const_fn)]`: `#[derive(Endian)]` also implements
+	/// `ConstEndian`, and `const fn` conversions.
+	pub const_fn: Option<Span>,
+	/// `#[endian(crate = "path")]`: the path at which generated code finds the
+	/// `endian_trait` crate.
+	pub krate: Option<Path>,
+}
+
+impl Container {
+	/// Collects the container options from the attributes on a type.
+	pub fn from_attrs<'a>(
+		attrs: impl IntoIterator<Item = &'a Attribute>,
+	) -> syn::Result<Self> {
+		let mut out = Self::default();
+		for_each_option(attrs, |key, input| match key.to_string().as_str() {
+			"no_padding" => set_once(&mut out.no_padding, key, key.span()),
+			"from_bytes" => set_once(&mut out.from_bytes, key, key.span()),
+			"wire" => set_once(&mut out.wire, key, key.span()),
+			"const_fn" => set_once(&mut out.const_fn, key, key.span()),
+			"size" => {
+				input.parse::<Token![=]>()?;
+				set_once(&mut out.size, key, input.parse()?)
+			},
+			"crate" => {
+				input.parse::<Token![=]>()?;
+				let path = input.parse::<LitStr>()?.parse::<Path>()?;
+				set_once(&mut out.krate, key, path)
+			},
+			"bit_order" => {
+				input.parse::<Token![=]>()?;
+				let lit = input.parse::<LitStr>()?;
+				let order = match lit.value().as_str() {
+					"msb" => BitOrder::Msb,
+					"lsb" => BitOrder::Lsb,
+					_ => return Err(syn::Error::new(
+						lit.span(),
+						"`bit_order` must be \"msb\" or \"lsb\"",
+					)),
+				};
+				set_once(&mut out.bit_order, key, order)
+			},
+			_ => Err(unknown(key)),
+		})?;
+		Ok(out)
+	}
+
+	/// Tests if any layout assertion was requested.
+	pub fn checks_layout(&self) -> bool {
+		self.padding_free().is_some() || self.size.is_some()
+	}
+
+	/// Finds the option, if any, that requires the type to have no padding.
+	pub fn padding_free(&self) -> Option<Span> {
+		self.no_padding.or(self.from_bytes)
+	}
+
+	/// The path to the `endian_trait` crate, which is `::endian_trait` unless
+	/// the type names another.
+	pub fn krate(&self) -> Path {
+		self.krate
+			.clone()
+			.unwrap_or_else(|| syn::parse_quote!(::endian_trait))
+	}
+}
+
+/// The placement of bit fields within the integer that holds them.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum BitOrder {
+	/// The first field occupies the most significant bits. This is the order
+	/// used by network protocol headers, and is the default.
+	Msb,
+	/// The first field occupies the least significant bits.
+	Lsb,
+}
+
+/// Options placed on an individual field of a struct.
+#[derive(Default)]
+pub struct Field {
+	/// `#[endian(bits = N)]`: the field occupies `N` bits of an integer shared
+	/// with its neighbors, rather than whole bytes of its own.
+	pub bits: Option<(Span, u32)>,
+	/// `#[endian(as = T)]`: the codec converts the field to `T` to encode it,
+	/// and back from `T` to decode it.
+	pub as_type: Option<Type>,
+	/// `#[endian(pad_before = N)]`: `N` zero bytes precede the field.
+	pub pad_before: Option<usize>,
+	/// `#[endian(align = N)]`: zero bytes precede the field, until its offset
+	/// from the start of the struct is a multiple of `N`.
+	pub align: Option<usize>,
+	/// `#[endian(reserved = N)]`: `N` bytes follow the field, which must be
+	/// zero.
+	pub reserved: Option<usize>,
+	/// `#[endian(len = "field")]`: the field is a sequence, whose length is the
+	/// value of an earlier integer field.
+	pub len: Option<LitStr>,
+	/// `#[endian(checksum = …)]`: the field holds a checksum of the bytes
+	/// before it, computed by this function.
+	pub checksum: Option<Checksum>,
+	/// `#[endian(magic = N)]` or `#[endian(const = EXPR)]`: the field always
+	/// holds this value on the wire, and reading checks it.
+	pub constant: Option<Expr>,
+	/// `#[endian(detect_order)]`: the field's `magic` number tells which byte
+	/// order the rest of the struct is in.
+	pub detect_order: Option<Span>,
+}
+
+impl Field {
+	/// Collects the field options from the attributes on a field, and checks
+	/// them against its type.
+	pub fn from_field(field: &syn::Field) -> syn::Result<Self> {
+		let out = Self::from_attrs(&field.attrs)?;
+		if let Some((span, bits)) = out.bits {
+			//  `Bits::from_bits` truncates to the field's type, so a wider
+			//  group would silently lose its high bits.
+			if let Some(width) = bit_width(&field.ty) {
+				if bits > width {
+					return Err(syn::Error::new(
+						span,
+						format!(
+							"`bits` cannot be wider than the field's {} bits",
+							width,
+						),
+					));
+				}
+			}
+		}
+		Ok(out)
+	}
+
+	/// Collects the field options from the attributes on a field.
+	fn from_attrs<'a>(
+		attrs: impl IntoIterator<Item = &'a Attribute>,
+	) -> syn::Result<Self> {
+		let mut out = Self::default();
+		//  The option that decides how the field's value is written, of which
+		//  a field may only have one.
+		let mut shape = None::<String>;
+		for_each_option(attrs, |key, input| match key.to_string().as_str() {
+			"bits" | "as" | "len" | "checksum" | "magic" | "const"
+				if shape.as_ref().is_some_and(|prev| key != prev) =>
+			{
+				Err(syn::Error::new(
+					key.span(),
+					format!(
+						"`{}` and `{}` cannot be placed on the same field",
+						shape.as_deref().unwrap_or_default(),
+						key,
+					),
+				))
+			},
+			"bits" => {
+				shape = Some(key.to_string());
+				input.parse::<Token![=]>()?;
+				let lit = input.parse::<LitInt>()?;
+				let bits = lit.base10_parse::<u32>()?;
+				if bits == 0 || bits > 64 {
+					return Err(syn::Error::new(
+						lit.span(),
+						"`bits` must be between 1 and 64",
+					));
+				}
+				set_once(&mut out.bits, key, (lit.span(), bits))
+			},
+			"as" => {
+				shape = Some(key.to_string());
+				input.parse::<Token![=]>()?;
+				set_once(&mut out.as_type, key, input.parse()?)
+			},
+			"pad_before" => {
+				input.parse::<Token![=]>()?;
+				let len = input.parse::<LitInt>()?.base10_parse()?;
+				set_once(&mut out.pad_before, key, len)
+			},
+			"align" => {
+				input.parse::<Token![=]>()?;
+				let lit = input.parse::<LitInt>()?;
+				let align = lit.base10_parse::<usize>()?;
+				if align == 0 {
+					return Err(syn::Error::new(
+						lit.span(),
+						"`align` must be at least 1",
+					));
+				}
+				set_once(&mut out.align, key, align)
+			},
+			"reserved" => {
+				input.parse::<Token![=]>()?;
+				let len = input.parse::<LitInt>()?.base10_parse()?;
+				set_once(&mut out.reserved, key, len)
+			},
+			"len" => {
+				shape = Some(key.to_string());
+				input.parse::<Token![=]>()?;
+				set_once(&mut out.len, key, input.parse()?)
+			},
+			"checksum" => {
+				shape = Some(key.to_string());
+				input.parse::<Token![=]>()?;
+				set_once(&mut out.checksum, key, checksum(input)?)
+			},
+			"magic" => {
+				shape = Some(key.to_string());
+				input.parse::<Token![=]>()?;
+				let lit = input.parse::<LitInt>()?;
+				set_once(&mut out.constant, key, Expr::Lit(ExprLit {
+					attrs: Vec::new(),
+					lit: lit.into(),
+				}))
+			},
+			"const" => {
+				shape = Some(key.to_string());
+				input.parse::<Token![=]>()?;
+				set_once(&mut out.constant, key, input.parse()?)
+			},
+			"detect_order" => set_once(&mut out.detect_order, key, key.span()),
+			_ => Err(unknown(key)),
+		})?;
+		if let Some(span) = out.detect_order {
+			if shape.as_deref() != Some("magic") {
+				return Err(syn::Error::new(
+					span,
+					"`detect_order` requires a `magic` number on the same field",
+				));
+			}
+		}
+		if let Some((span, _)) = out.bits {
+			//  Padding goes between whole bytes, and bit fields share theirs.
+			if out.pad_before.is_some()
+				|| out.align.is_some()
+				|| out.reserved.is_some()
+			{
+				return Err(syn::Error::new(
+					span,
+					"bit fields cannot have `pad_before`, `align`, or \
+					`reserved`",
+				));
+			}
+		}
+		Ok(out)
+	}
+}
+
+/// The function that computes a checksum field.
+pub enum Checksum {
+	/// One of the algorithms in `endian_trait::checksum`.
+	Builtin(Ident),
+	/// The function at a path of the user's choosing.
+	Custom(Path),
+}
+
+impl Checksum {
+	/// The path to the function, given the path to the `endian_trait` crate.
+	pub fn func(self, krate: &Path) -> Path {
+		match self {
+			Checksum::Builtin(name) => {
+				syn::parse_quote!(#krate::checksum::#name)
+			},
+			Checksum::Custom(path) => path,
+		}
+	}
+}
+
+/// Parses the algorithm of a `checksum` option.
+fn checksum(input: ParseStream) -> syn::Result<Checksum> {
+	let name = input.parse::<Ident>()?;
+	match name.to_string().as_str() {
+		"crc32" | "crc16_ccitt" | "internet" => Ok(Checksum::Builtin(name)),
+		"custom" => {
+			let func;
+			syn::parenthesized!(func in input);
+			Ok(Checksum::Custom(func.parse()?))
+		},
+		_ => Err(syn::Error::new(
+			name.span(),
+			"expected `crc32`, `crc16_ccitt`, `internet`, or `custom(path)`",
+		)),
+	}
+}
+
+/// The number of bits that a field of a primitive type can hold, or `None` if
+/// the type is not one whose width is known here.
+fn bit_width(ty: &Type) -> Option<u32> {
+	let ident = match ty {
+		Type::Path(path) if path.qself.is_none() => path.path.get_ident()?,
+		_ => return None,
+	};
+	Some(match ident.to_string().as_str() {
+		"bool" => 1,
+		"u8" | "i8" => 8,
+		"u16" | "i16" => 16,
+		"u32" | "i32" => 32,
+		"u64" | "i64" => 64,
+		_ => return None,
+	})
+}
+
+/// Runs `func` on the key of each option in each `#[endian(…)]` attribute.
+///
+/// `func` receives the parse stream positioned just after the key, and is
+/// responsible for consuming any `= value` that belongs to that key.
+fn for_each_option<'a>(
+	attrs: impl IntoIterator<Item = &'a Attribute>,
+	mut func: impl FnMut(&Ident, ParseStream) -> syn::Result<()>,
+) -> syn::Result<()> {
+	for attr in attrs.into_iter().filter(|a| a.path.is_ident("endian")) {
+		attr.parse_args_with(|input: ParseStream| {
+			while !input.is_empty() {
+				//  Keys such as `as` and `crate` are keywords, so they must be
+				//  parsed as raw identifiers.
+				let key = input.call(Ident::parse_any)?;
+				func(&key, input)?;
+				if !input.is_empty() {
+					input.parse::<Token![,]>()?;
+				}
+			}
+			Ok(())
+		})?;
+	}
+	Ok(())
+}
+
+/// Stores an option's value, refusing to overwrite an earlier one.
+fn set_once<T>(slot: &mut Option<T>, key: &Ident, value: T) -> syn::Result<()> {
+	if slot.is_some() {
+		return Err(syn::Error::new(
+			key.span(),
+			format!("`#[endian({})]` may only be given once", key),
+		));
+	}
+	*slot = Some(value);
+	Ok(())
+}
+
+/// Builds the error for an option this position does not accept.
+fn unknown(key: &Ident) -> syn::Error {
+	syn::Error::new(
+		key.span(),
+		format!("`{}` is not a valid `#[endian]` option here", key),
+	)
+}
@@ -0,0 +1,323 @@
+/*! Code generation for `#[derive(Codec)]`
+
+Structs are encoded as each of their fields in declaration order, with nothing
+between them but the padding their options ask for, as laid out by the `layout`
+module. Reserved bytes that are not zero are an error when decoding. Enums are
+encoded as their `repr` integer, and decoding rejects integers that are not one
+of the enum's discriminants.
+!*/
+
+use crate::attrs::{
+	self,
+	BitOrder,
+};
+
+use crate::layout::{
+	self,
+	Backend,
+	Item,
+};
+
+use pm2::{
+	Span,
+	TokenStream,
+};
+
+use quote::{
+	ToTokens,
+	quote,
+};
+
+use syn::{
+	Data,
+	DataEnum,
+	DataStruct,
+	DeriveInput,
+	Fields,
+	Generics,
+	Ident,
+	Path,
+};
+
+/// Moves values through `io` streams, with `Codec`.
+struct Streams {
+	krate: Path,
+}
+
+impl Backend for Streams {
+	fn krate(&self) -> &Path {
+		&self.krate
+	}
+
+	fn put(&self, value: TokenStream) -> TokenStream {
+		let krate = &self.krate;
+		quote! {
+			#krate::codec::Codec::encode(#value, order, writer)?;
+		}
+	}
+
+	fn take(&self, ty: Option<&dyn ToTokens>) -> TokenStream {
+		let krate = &self.krate;
+		match ty {
+			Some(ty) => quote! {
+				<#ty as #krate::codec::Codec>::decode(order, reader)?
+			},
+			None => quote! {
+				#krate::codec::Codec::decode(order, reader)?
+			},
+		}
+	}
+
+	fn put_failed(&self, msg: &str) -> TokenStream {
+		let krate = &self.krate;
+		quote! {
+			return ::core::result::Result::Err(
+				#krate::codec::invalid_input(#msg),
+			)
+		}
+	}
+
+	fn take_failed(&self, msg: &str) -> TokenStream {
+		let krate = &self.krate;
+		quote! {
+			return ::core::result::Result::Err(
+				#krate::codec::invalid_data(#msg),
+			)
+		}
+	}
+
+	fn pad(&self, len: TokenStream) -> TokenStream {
+		let krate = &self.krate;
+		quote! {
+			#krate::codec::pad(#len, writer)?;
+		}
+	}
+
+	fn skip(&self, len: TokenStream, check: Option<&str>) -> TokenStream {
+		let krate = &self.krate;
+		match check {
+			Some(msg) => {
+				let fail = self.take_failed(msg);
+				quote! {
+					if !#krate::codec::skip(#len, reader)? {
+						#fail;
+					}
+				}
+			},
+			None => quote! {
+				#krate::codec::skip(#len, reader)?;
+			},
+		}
+	}
+
+	fn put_seq(&self, items: TokenStream) -> TokenStream {
+		let krate = &self.krate;
+		quote! {
+			#krate::codec::encode_seq(#items, order, writer)?;
+		}
+	}
+
+	fn take_seq(&self, len: TokenStream) -> TokenStream {
+		let krate = &self.krate;
+		quote! {
+			#krate::codec::decode_seq(#len, order, reader)?
+		}
+	}
+
+	fn track_write(&self, record: bool) -> TokenStream {
+		let krate = &self.krate;
+		let stream = tracker(record);
+		quote! {
+			let writer = &mut #krate::codec::#stream::new(writer);
+		}
+	}
+
+	fn track_read(&self, record: bool) -> TokenStream {
+		let krate = &self.krate;
+		let stream = tracker(record);
+		quote! {
+			let reader = &mut #krate::codec::#stream::new(reader);
+		}
+	}
+
+	fn written(&self) -> TokenStream {
+		quote!(writer.count())
+	}
+
+	fn been_read(&self) -> TokenStream {
+		quote!(reader.count())
+	}
+
+	fn read_bytes(&self) -> TokenStream {
+		quote!(reader.bytes())
+	}
+
+	fn put_checksum(
+		&self,
+		ty: &TokenStream,
+		func: &Path,
+		slot: &Ident,
+	) -> (TokenStream, TokenStream) {
+		let put = self.put(quote!(&#slot));
+		(
+			quote! {
+				let #slot: #ty = #func(writer.bytes());
+				#put
+			},
+			TokenStream::new(),
+		)
+	}
+
+}
+
+/// The stream adapter that keeps track of the bytes moved through it.
+fn tracker(record: bool) -> Ident {
+	let name = if record { "Recorded" } else { "Counted" };
+	Ident::new(name, Span::call_site())
+}
+
+/// Generate the Codec impl for a struct or data-less enum.
+pub fn derive(ast: &DeriveInput) -> syn::Result<TokenStream> {
+	let name = &ast.ident;
+	let (g_impl, g_ty, g_where) = ast.generics.split_for_impl();
+	//  The options are shared with `#[derive(Endian)]`, and are checked here
+	//  even where they do not affect the codec.
+	let container = attrs::Container::from_attrs(&ast.attrs)?;
+	let krate = container.krate();
+	let mut detect = None;
+	let (encode, decode) = match ast.data {
+		Data::Enum(DataEnum { ref variants, .. }) => {
+			if container.bit_order.is_some() {
+				return Err(syn::Error::new(
+					name.span(),
+					"`#[endian(bit_order)]` is only supported on structs",
+				));
+			}
+			let kind = super::enum_repr(name, &ast.attrs, variants, "Codec")?;
+			let vars = variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+			let msg = format!("not a discriminant of `{}`", name);
+			(
+				quote! {
+					let repr: #kind = match *self {
+						#( Self::#vars => Self::#vars as #kind, )*
+					};
+					#krate::codec::Codec::encode(&repr, order, writer)
+				},
+				quote! {
+					let repr: #kind =
+						#krate::codec::Codec::decode(order, reader)?;
+					#( if repr == Self::#vars as #kind {
+						return Ok(Self::#vars);
+					} )*
+					Err(#krate::codec::invalid_data(#msg))
+				},
+			)
+		},
+		Data::Struct(DataStruct { ref fields, .. }) => {
+			let items = layout::plan(fields, &krate)?;
+			detect = detect_order(name, &ast.generics, fields, &items, &krate)?;
+			let (write, read) = layout::gen_struct(
+				fields,
+				&items,
+				container.bit_order.unwrap_or(BitOrder::Msb),
+				&Streams { krate: krate.clone() },
+			)?;
+			(
+				quote! {
+					#write
+					Ok(())
+				},
+				quote! {
+					Ok({ #read })
+				},
+			)
+		},
+		Data::Union(..) => return Err(syn::Error::new(
+			name.span(),
+			"Rust does not currently permit `#[derive(Trait)]` attributes on \
+			`union` types",
+		)),
+	};
+	Ok(quote! {
+		impl #g_impl #krate::codec::Codec for #name #g_ty #g_where {
+			#[allow(unused_variables)]
+			fn encode<__W>(
+				&self,
+				order: #krate::Endianness,
+				writer: &mut __W,
+			) -> ::std::io::Result<()>
+			where __W: ::std::io::Write + ?::core::marker::Sized {
+				#encode
+			}
+
+			#[allow(unused_variables)]
+			fn decode<__R>(
+				order: #krate::Endianness,
+				reader: &mut __R,
+			) -> ::std::io::Result<Self>
+			where __R: ::std::io::Read + ?::core::marker::Sized {
+				#decode
+			}
+		}
+
+		#detect
+	})
+}
+
+/// Generate the DetectOrder impl for a struct whose first field is a magic
+/// number marked `detect_order`, if it has one.
+fn detect_order(
+	name: &Ident,
+	generics: &Generics,
+	fields: &Fields,
+	items: &[Item],
+	krate: &Path,
+) -> syn::Result<Option<TokenStream>> {
+	let first = match fields.iter().next() {
+		Some(field) => field,
+		None => return Ok(None),
+	};
+	if attrs::Field::from_field(first)?.detect_order.is_none() {
+		return Ok(None);
+	}
+	let magic = match items.first() {
+		Some(Item::Const(0, magic)) => magic,
+		_ => return Err(syn::Error::new_spanned(
+			first,
+			"`detect_order` must be on a leading `magic` number",
+		)),
+	};
+	let (g_impl, g_ty, g_where) = generics.split_for_impl();
+	let ty = &first.ty;
+	let msg = format!("not the magic number of `{}` in either byte order", name);
+	Ok(Some(quote! {
+		impl #g_impl #krate::codec::DetectOrder
+		for #name #g_ty #g_where {
+			fn decode_detect<__R>(
+				reader: &mut __R,
+			) -> ::std::io::Result<(Self, #krate::Endianness)>
+			where __R: ::std::io::Read + ?::core::marker::Sized {
+				let mut head = [0u8; ::core::mem::size_of::<#ty>()];
+				::std::io::Read::read_exact(reader, &mut head)?;
+				let order = if <#ty>::from_be_bytes(head) == #magic {
+					#krate::Endianness::Big
+				}
+				else if <#ty>::from_le_bytes(head) == #magic {
+					#krate::Endianness::Little
+				}
+				else {
+					return ::core::result::Result::Err(
+						#krate::codec::invalid_data(#msg),
+					);
+				};
+				//  The magic number is read again, and checked, by `decode`.
+				let mut reader = ::std::io::Read::chain(&head[..], reader);
+				let value = <Self as #krate::codec::Codec>::decode(
+					order,
+					&mut reader,
+				)?;
+				::core::result::Result::Ok((value, order))
+			}
+		}
+	}))
+}
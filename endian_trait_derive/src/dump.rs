@@ -0,0 +1,90 @@
+/*! Code generation for `#[derive(EndianDump)]`
+
+Structs dump each of their fields at its offset in the struct, under its name
+or its index. Fields marked `#[endian(as = T)]` are left out, as `Endian` does
+not convert them. Enums dump their `repr` integer.
+!*/
+
+use crate::attrs;
+
+use pm2::TokenStream;
+
+use quote::{
+	quote,
+	quote_spanned,
+};
+
+use syn::{
+	Data,
+	DataEnum,
+	DataStruct,
+	DeriveInput,
+	Member,
+	spanned::Spanned,
+};
+
+/// Generate the EndianDump impl for a struct or data-less enum.
+pub fn derive(ast: &DeriveInput) -> syn::Result<TokenStream> {
+	let name = &ast.ident;
+	let label = name.to_string();
+	let krate = attrs::Container::from_attrs(&ast.attrs)?.krate();
+	let mut generics = ast.generics.clone();
+	let body = match ast.data {
+		Data::Enum(DataEnum { ref variants, .. }) => {
+			let kind =
+				super::enum_repr(name, &ast.attrs, variants, "EndianDump")?;
+			let vars = variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+			quote! {
+				let repr: #kind = match *self {
+					#( Self::#vars => Self::#vars as #kind, )*
+				};
+				dumper.scalar(#label, repr)
+			}
+		},
+		Data::Struct(DataStruct { ref fields, .. }) => {
+			let mut lines = Vec::new();
+			let preds = generics.make_where_clause();
+			for (field, member) in fields.iter().zip(super::members(fields)) {
+				if attrs::Field::from_field(field)?.as_type.is_some() {
+					continue;
+				}
+				let label = match member {
+					Member::Named(ref ident) => ident.to_string(),
+					Member::Unnamed(ref idx) => idx.index.to_string(),
+				};
+				let ty = &field.ty;
+				let krate = super::krate_at(&krate, ty.span());
+				preds.predicates.push(syn::parse2(quote_spanned! {ty.span()=>
+					#ty: #krate::dump::EndianDump
+				})?);
+				lines.push(quote! {
+					dumper.field(
+						#label,
+						::core::mem::offset_of!(Self, #member),
+						&self.#member,
+					)?;
+				});
+			}
+			quote! {
+				#( #lines )*
+				::core::result::Result::Ok(())
+			}
+		},
+		Data::Union(..) => return Err(syn::Error::new(
+			name.span(),
+			"Rust does not currently permit `#[derive(Trait)]` attributes on \
+			`union` types",
+		)),
+	};
+	let (g_impl, g_ty, g_where) = generics.split_for_impl();
+	Ok(quote! {
+		impl #g_impl #krate::dump::EndianDump for #name #g_ty #g_where {
+			fn dump(
+				&self,
+				dumper: &mut #krate::dump::Dumper<'_, '_>,
+			) -> ::core::fmt::Result {
+				#body
+			}
+		}
+	})
+}
@@ -0,0 +1,503 @@
+/*! The wire layout of a struct's fields
+
+`#[derive(Codec)]` and `#[derive(Endian)]` with `#[endian(wire)]` both write a
+struct as its fields in declaration order, with nothing between them, and read
+it back the same way. They differ only in where the bytes go: the codec moves
+them through `io` streams, and the wire layout through byte slices. This module
+walks the fields once for both, and asks a `Backend` for the code that moves
+each single value.
+
+Fields marked `#[endian(as = T)]` are converted to `T` with `From`, and written
+as a `T`. Reading produces a `T`, and converts it back with `TryFrom`.
+
+Consecutive fields marked `#[endian(bits = N)]` are packed into one integer,
+which is written in place of them. A group ends as soon as its fields fill a
+`u8`, `u16`, `u32`, or `u64`, and it is an error for a group to end at any other
+width.
+
+The padding options put zero bytes around a field. `pad_before = N` and
+`align = N` come before it, and are passed over when reading. `reserved = N`
+comes after it, and backends that can report errors check that it is still
+zero. Alignment counts from the start of the struct's own wire form, so a struct
+nested in another aligns its fields relative to itself.
+
+A field marked `#[endian(len = "count")]` is a sequence, such as a `Vec<T>`,
+whose elements are written one after another with no length of their own. The
+named field, which must come earlier and be an integer, holds the length, and
+writing checks that it matches. Only backends that move variable amounts of
+data can write these fields.
+
+A field marked `#[endian(checksum = …)]` is written as the checksum of every
+byte of the struct's wire form before it, whatever value it holds, and reading
+fails if the stored checksum does not match.
+
+A field marked `#[endian(magic = N)]` or `#[endian(const = EXPR)]` is always
+written as that value, and reading fails if it holds anything else.
+!*/
+
+use crate::attrs::{
+	self,
+	BitOrder,
+};
+
+use pm2::{
+	Literal,
+	Span,
+	TokenStream,
+};
+
+use quote::{
+	ToTokens,
+	format_ident,
+	quote,
+};
+
+use syn::{
+	Expr,
+	Fields,
+	Ident,
+	LitStr,
+	Member,
+	Path,
+	Type,
+};
+
+/// Produces the code that moves single values to and from the wire.
+pub trait Backend {
+	/// The path to the `endian_trait` crate.
+	fn krate(&self) -> &Path;
+
+	/// A statement that writes the value of `value`, which is a reference.
+	fn put(&self, value: TokenStream) -> TokenStream;
+
+	/// An expression that reads a value of type `ty`, or of the type that the
+	/// expression's use requires.
+	fn take(&self, ty: Option<&dyn ToTokens>) -> TokenStream;
+
+	/// A diverging expression that abandons writing, with a message.
+	fn put_failed(&self, msg: &str) -> TokenStream;
+
+	/// A diverging expression that abandons reading, with a message.
+	fn take_failed(&self, msg: &str) -> TokenStream;
+
+	/// A statement that writes `len` zero bytes.
+	fn pad(&self, len: TokenStream) -> TokenStream;
+
+	/// A statement that passes over `len` bytes. When `check` has a message,
+	/// the bytes must be zero, and reading abandons with that message if they
+	/// are not.
+	fn skip(&self, len: TokenStream, check: Option<&str>) -> TokenStream;
+
+	/// A statement that writes each element of `items`, which is a slice.
+	fn put_seq(&self, items: TokenStream) -> TokenStream;
+
+	/// An expression that reads `len` elements into the collection that the
+	/// expression's use requires.
+	fn take_seq(&self, len: TokenStream) -> TokenStream;
+
+	/// Statements that begin counting the bytes written. When `record` is set,
+	/// checksums must also be able to reach the bytes themselves.
+	fn track_write(&self, record: bool) -> TokenStream;
+
+	/// Statements that begin counting the bytes read. When `record` is set,
+	/// the bytes themselves must be kept as well.
+	fn track_read(&self, record: bool) -> TokenStream;
+
+	/// An expression for the number of bytes written so far.
+	fn written(&self) -> TokenStream;
+
+	/// An expression for the number of bytes read so far.
+	fn been_read(&self) -> TokenStream;
+
+	/// An expression for the bytes read so far, as a slice.
+	fn read_bytes(&self) -> TokenStream;
+
+	/// Writes a checksum of type `ty`, computed by `func` over the bytes
+	/// written so far. This produces a statement for the checksum's place in
+	/// the struct, and statements for after all of the fields are written,
+	/// which may use `slot` to pass state between them.
+	fn put_checksum(
+		&self,
+		ty: &TokenStream,
+		func: &Path,
+		slot: &Ident,
+	) -> (TokenStream, TokenStream);
+}
+
+/// A unit of a struct's wire form.
+pub enum Item {
+	/// A field written as itself, or as the type named in its
+	/// `#[endian(as = T)]` option. This carries the field's position in the
+	/// struct.
+	Whole(usize, Option<Type>),
+	/// Bit fields sharing one integer, as their positions and widths.
+	Bits(Vec<(usize, u32)>),
+	/// A number of zero bytes, from `#[endian(pad_before = N)]`.
+	Pad(usize),
+	/// Zero bytes up to the next multiple of a number, from
+	/// `#[endian(align = N)]`.
+	Align(usize),
+	/// A number of bytes that must be zero, from `#[endian(reserved = N)]`.
+	Reserved(usize),
+	/// A sequence field, and the position of the field that holds its length,
+	/// from `#[endian(len = "…")]`.
+	Seq(usize, usize),
+	/// A field holding a checksum of everything before it, and the function
+	/// that computes it, from `#[endian(checksum = …)]`.
+	Checksum(usize, Path),
+	/// A field that always holds the same value, from `#[endian(magic = N)]`
+	/// or `#[endian(const = EXPR)]`.
+	Const(usize, Expr),
+}
+
+impl Item {
+	/// The type that this item is written as, if it holds a value of a fixed
+	/// size.
+	pub fn wire_type(&self, fields: &Fields) -> Option<TokenStream> {
+		match *self {
+			Item::Whole(_, Some(ref ty)) => Some(ty.to_token_stream()),
+			Item::Whole(n, None)
+			| Item::Checksum(n, _)
+			| Item::Const(n, _) => Some(fields.iter()
+				.nth(n)
+				.expect("items only refer to fields that exist")
+				.ty
+				.to_token_stream()),
+			Item::Bits(ref group) => {
+				let total = group.iter().map(|&(_, w)| w).sum::<u32>();
+				Some(format_ident!("u{}", total).into_token_stream())
+			},
+			Item::Pad(_)
+			| Item::Align(_)
+			| Item::Reserved(_)
+			| Item::Seq(..) => None,
+		}
+	}
+}
+
+/// Generate the bodies that write and read a struct's fields.
+///
+/// The write body is a sequence of statements. The read body is a sequence of
+/// statements followed by a `Self { … }` expression.
+pub fn gen_struct(
+	fields: &Fields,
+	items: &[Item],
+	bit_order: BitOrder,
+	backend: &impl Backend,
+) -> syn::Result<(TokenStream, TokenStream)> {
+	let members = super::members(fields);
+	//  Reading puts each field into a local before building the struct, as a
+	//  bit group produces several fields from one read.
+	let locals = (0 .. members.len())
+		.map(|n| format_ident!("__field{}", n))
+		.collect::<Vec<_>>();
+	let mut write = Vec::new();
+	let mut read = Vec::new();
+	//  Checksums run after the fields that precede them are written, which may
+	//  not be until the end.
+	let mut finish = Vec::new();
+	//  Only alignment and checksums need to know where they are, so only
+	//  structs that use them pay for keeping track.
+	let record = items.iter().any(|item| matches!(item, Item::Checksum(..)));
+	if record || items.iter().any(|item| matches!(item, Item::Align(_))) {
+		write.push(backend.track_write(record));
+		read.push(backend.track_read(record));
+	}
+	let (written, been_read) = (backend.written(), backend.been_read());
+	let krate = backend.krate();
+	for item in items {
+		match *item {
+			Item::Whole(n, Some(ref ty)) => {
+				let (member, local) = (&members[n], &locals[n]);
+				let msg = format!(
+					"`{}` cannot hold the decoded value",
+					member_name(member),
+				);
+				write.push(backend.put(quote! {
+					&<#ty as ::core::convert::From<_>>::from(
+						::core::clone::Clone::clone(&self.#member),
+					)
+				}));
+				let take = backend.take(Some(ty));
+				let fail = backend.take_failed(&msg);
+				read.push(quote! {
+					let #local = match ::core::convert::TryFrom::try_from(
+						#take,
+					) {
+						::core::result::Result::Ok(val) => val,
+						::core::result::Result::Err(_) => #fail,
+					};
+				});
+			},
+			Item::Whole(n, None) => {
+				let (member, local) = (&members[n], &locals[n]);
+				write.push(backend.put(quote!(&self.#member)));
+				let take = backend.take(None);
+				read.push(quote! {
+					let #local = #take;
+				});
+			},
+			Item::Pad(len) => {
+				let len = Literal::usize_unsuffixed(len);
+				write.push(backend.pad(quote!(#len)));
+				read.push(backend.skip(quote!(#len), None));
+			},
+			Item::Align(align) => {
+				let align = Literal::usize_unsuffixed(align);
+				write.push(backend.pad(quote! {
+					(#align - #written % #align) % #align
+				}));
+				read.push(backend.skip(
+					quote!((#align - #been_read % #align) % #align),
+					None,
+				));
+			},
+			Item::Reserved(len) => {
+				let msg = format!("{} reserved bytes are not zero", len);
+				let len = Literal::usize_unsuffixed(len);
+				write.push(backend.pad(quote!(#len)));
+				read.push(backend.skip(quote!(#len), Some(&msg)));
+			},
+			Item::Checksum(n, ref func) => {
+				let (member, local) = (&members[n], &locals[n]);
+				let ty = item.wire_type(fields)
+					.expect("checksum fields have a wire type");
+				let slot = format_ident!("__sum{}", n);
+				let (put, after) = backend.put_checksum(&ty, func, &slot);
+				write.push(put);
+				finish.push(after);
+				let bytes = backend.read_bytes();
+				let take = backend.take(Some(&ty));
+				let fail = backend.take_failed(&format!(
+					"`{}` does not match the checksum of the bytes before it",
+					member_name(member),
+				));
+				read.push(quote! {
+					let #slot: #ty = #func(#bytes);
+					let #local: #ty = #take;
+					if #local != #slot {
+						#fail;
+					}
+				});
+			},
+			Item::Const(n, ref value) => {
+				let (member, local) = (&members[n], &locals[n]);
+				let ty = item.wire_type(fields)
+					.expect("constant fields have a wire type");
+				let put = backend.put(quote!(&__const));
+				write.push(quote! {
+					{
+						let __const: #ty = #value;
+						#put
+					}
+				});
+				let take = backend.take(Some(&ty));
+				let fail = backend.take_failed(&format!(
+					"`{}` does not hold its expected value",
+					member_name(member),
+				));
+				read.push(quote! {
+					let #local: #ty = #take;
+					if #local != #value {
+						#fail;
+					}
+				});
+			},
+			Item::Seq(n, count) => {
+				let (member, local) = (&members[n], &locals[n]);
+				let (len, len_local) = (&members[count], &locals[count]);
+				let put_fail = backend.put_failed(&format!(
+					"`{}` does not hold the length of `{}`",
+					member_name(len),
+					member_name(member),
+				));
+				let take_fail = backend.take_failed(&format!(
+					"`{}` is too large for a length",
+					member_name(len),
+				));
+				let put = backend.put_seq(quote!(&self.#member[..]));
+				write.push(quote! {
+					if <usize as ::core::convert::TryFrom<_>>::try_from(
+						self.#len,
+					).ok() != ::core::option::Option::Some(self.#member.len()) {
+						#put_fail;
+					}
+					#put
+				});
+				let take = backend.take_seq(quote!(__len));
+				read.push(quote! {
+					let #local = {
+						let __len = match <usize as ::core::convert::TryFrom<_>>
+							::try_from(#len_local)
+						{
+							::core::result::Result::Ok(len) => len,
+							::core::result::Result::Err(_) => #take_fail,
+						};
+						#take
+					};
+				});
+			},
+			Item::Bits(ref group) => {
+				let kind = item.wire_type(fields)
+					.expect("bit groups have a wire type");
+				let total = group.iter().map(|&(_, w)| w).sum::<u32>();
+				let mut offset = 0;
+				let mut put = Vec::new();
+				let mut take = Vec::new();
+				for &(n, width) in group {
+					let shift = Literal::u32_unsuffixed(match bit_order {
+						BitOrder::Msb => total - offset - width,
+						BitOrder::Lsb => offset,
+					});
+					offset += width;
+					let mask = Literal::u64_unsuffixed(u64::MAX >> (64 - width));
+					let (member, local) = (&members[n], &locals[n]);
+					let fail = backend.put_failed(&format!(
+						"`{}` does not fit in {} bits",
+						member_name(member),
+						width,
+					));
+					let width = Literal::u32_unsuffixed(width);
+					put.push(quote! {
+						__bits |= (match #krate::wire::Bits::to_bits(
+							self.#member,
+							#width,
+						) {
+							::core::option::Option::Some(bits) => bits,
+							::core::option::Option::None => #fail,
+						} << #shift) as #kind;
+					});
+					take.push(quote! {
+						let #local = #krate::wire::Bits::from_bits(
+							(__bits as u64 >> #shift) & #mask,
+							#width,
+						);
+					});
+				}
+				let put_bits = backend.put(quote!(&__bits));
+				write.push(quote! {
+					let mut __bits: #kind = 0;
+					#( #put )*
+					#put_bits
+				});
+				let take_bits = backend.take(Some(&kind));
+				read.push(quote! {
+					let __bits: #kind = #take_bits;
+					#( #take )*
+				});
+			},
+		}
+	}
+	Ok((
+		quote! {
+			#( #write )*
+			#( #finish )*
+		},
+		quote! {
+			#( #read )*
+			Self { #( #members: #locals, )* }
+		},
+	))
+}
+
+/// Sort a struct's fields into whole fields and groups of bit fields.
+///
+/// `krate` is the path to the `endian_trait` crate, which holds the built-in
+/// checksum functions.
+pub fn plan(fields: &Fields, krate: &Path) -> syn::Result<Vec<Item>> {
+	let mut items = Vec::new();
+	let mut group = Vec::new();
+	let mut total = 0;
+	//  The span of the most recent `bits` option, for reporting a group that
+	//  does not fill its integer.
+	let mut last = Span::call_site();
+	for (n, field) in fields.iter().enumerate() {
+		let opts = attrs::Field::from_field(field)?;
+		match opts.bits {
+			Some((span, width)) => {
+				group.push((n, width));
+				total += width;
+				last = span;
+				match total {
+					8 | 16 | 32 | 64 => {
+						items.push(Item::Bits(group.split_off(0)));
+						total = 0;
+					},
+					65 ..= u32::MAX => return Err(unfilled(last, total)),
+					_ => {},
+				}
+			},
+			None if group.is_empty() => {
+				if let Some(span) = opts.detect_order {
+					let padded = opts.pad_before.is_some()
+						|| opts.align.is_some()
+						|| opts.reserved.is_some();
+					if n != 0 || padded {
+						return Err(syn::Error::new(
+							span,
+							"`detect_order` must be on the first field, with \
+							no padding or reserved bytes around it",
+						));
+					}
+				}
+				items.extend(opts.pad_before.map(Item::Pad));
+				items.extend(opts.align.map(Item::Align));
+				items.push(if let Some(len) = opts.len {
+					Item::Seq(n, find_len(fields, n, &len)?)
+				}
+				else if let Some(sum) = opts.checksum {
+					Item::Checksum(n, sum.func(krate))
+				}
+				else if let Some(value) = opts.constant {
+					Item::Const(n, value)
+				}
+				else {
+					Item::Whole(n, opts.as_type)
+				});
+				items.extend(opts.reserved.map(Item::Reserved));
+			},
+			None => return Err(unfilled(last, total)),
+		}
+	}
+	if !group.is_empty() {
+		return Err(unfilled(last, total));
+	}
+	Ok(items)
+}
+
+/// Finds the field named by a `len` option, among those before field `n`.
+fn find_len(fields: &Fields, n: usize, len: &LitStr) -> syn::Result<usize> {
+	let name = len.value();
+	fields.iter()
+		.take(n)
+		.enumerate()
+		.find(|(idx, field)| match field.ident {
+			Some(ref ident) => *ident == name,
+			None => idx.to_string() == name,
+		})
+		.map(|(idx, _)| idx)
+		.ok_or_else(|| syn::Error::new(
+			len.span(),
+			format!("`len` must name an earlier field, not `{}`", name),
+		))
+}
+
+/// Builds the error for a group of bit fields that does not fill an integer.
+fn unfilled(span: Span, total: u32) -> syn::Error {
+	syn::Error::new(
+		span,
+		format!(
+			"consecutive bit fields must fill a `u8`, `u16`, `u32`, or `u64`, \
+			but this group has {} bits",
+			total,
+		),
+	)
+}
+
+/// The name of a field, as written in its declaration.
+fn member_name(member: &Member) -> String {
+	match member {
+		Member::Named(ident) => ident.to_string(),
+		Member::Unnamed(idx) => idx.index.to_string(),
+	}
+}
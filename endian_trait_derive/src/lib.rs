@@ -5,7 +5,10 @@ macro simply takes the component fields of the type on which it is annotated and
 attempts to call the Endian conversion on each field. This means that Endian can
 only be derived on types composed of types that are all themselves Endian.
 Attempting to derive Endian on a type with a non-Endian type in it will result
-in a compilation error.
+in a compilation error, which points at the offending field and names its type.
+Generic type parameters need not be bound by `Endian`: the impl requires each
+field's type to be `Endian` in its `where` clause, so a generic struct is
+`Endian` whenever its fields are.
 
 # Use Case
 
@@ -29,7 +32,21 @@ and this crate will be pulled in transitively.
 
 By itself, this crate provides a custom-derive macro to emit a trait impl block
 that is syntactically valid but will fail to compile without the `endian_trait`
-crate and `Endian` trait in scope.
+crate. The generated code names everything by its full path, starting at
+`::endian_trait`, so the `Endian` trait itself need not be in scope, or may be
+imported under another name.
+
+A crate that re-exports `endian_trait`, and whose users may not depend on it
+directly, can point the generated code at its re-export instead with the
+`#[endian(crate = "path")]` attribute:
+
+```rust,ignore
+#[derive(my_facade::Endian)]
+#[endian(crate = "my_facade::endian_trait")]
+struct Foo {
+    bar: u32,
+}
+```
 
 ```rust
 extern crate endian_trait;
@@ -47,6 +64,97 @@ struct Foo<A: Endian, B: Endian> {
 }
 # fn main() {}
 ```
+
+# Attributes
+
+Structs may carry `#[endian(…)]` attributes that add compile-time checks on
+their layout:
+
+- `#[endian(no_padding)]` asserts that the struct is exactly as wide as the sum
+  of its fields, so that its bytes can be sent over the wire without leaking
+  uninitialized padding.
+- `#[endian(size = N)]` asserts that the struct is exactly `N` bytes wide.
+
+- `#[endian(from_bytes)]` asserts the same as `no_padding`, and additionally
+  requires that every field accepts any bit pattern.
+
+These checks are `const` items, so they cannot be placed on generic structs.
+
+Structs with no padding, and all enums, are marked `AsBytes`; structs marked
+`from_bytes` are also marked `FromBytes`. These marker traits allow the
+`endian_trait::bytes` module to view them as byte slices without `unsafe`.
+
+# Wire
+
+Types marked `#[endian(wire)]` also implement `endian_trait::wire::Wire`, which
+reads and writes a fixed-size wire form in byte slices. Structs are written as
+each of their fields in declaration order, with no padding, however they are
+laid out in memory, and enums are written as their `repr` integer.
+
+# Const
+
+Structs marked `#[endian(const_fn)]` also implement
+`endian_trait::ConstEndian`, which lets `endian_trait::const_be` and
+`endian_trait::const_le` convert them in `const` contexts, and gain inherent
+`to_be_const`, `to_le_const`, `from_be_const`, and `from_le_const` methods that
+call those functions. Every converted field must be `ConstEndian` as well, and
+the struct cannot be generic.
+
+# Dump
+
+`#[derive(EndianDump)]` implements `endian_trait::dump::EndianDump`, which
+formats a value's converted representation as an annotated hex dump. Structs
+report each field under its name, at its offset from `core::mem::offset_of!`,
+and leave out fields marked `#[endian(as = T)]`. Enums report their `repr`
+integer.
+
+# Codec
+
+This crate also provides `#[derive(Codec)]`, which implements the
+`endian_trait::codec::Codec` trait. Structs are written as each of their fields
+in declaration order, with no padding, and enums with an integer `repr` are
+written as that integer.
+
+Consecutive struct fields marked `#[endian(bits = N)]` are packed into a single
+`u8`, `u16`, `u32`, or `u64`, which is written in their place. The first field
+takes the most significant bits, or the least significant bits if the struct is
+marked `#[endian(bit_order = "lsb")]`. `#[derive(Endian)]` rejects these options
+unless the struct is marked `#[endian(wire)]`, and its `Wire` impl packs bit
+fields the same way. A field cannot have more bits than its type holds.
+
+A field marked `#[endian(as = T)]` is encoded by converting it into a `T` with
+`From`, and decoded by converting a `T` back with `TryFrom`. This lets types
+without a fixed layout, such as `Duration`, appear in derived structs.
+`#[derive(Endian)]` cannot convert a field through `T`, so it only accepts
+`Duration` fields marked `#[endian(as = WireDuration)]`, and moves them into its
+result without converting them.
+
+Fields can also ask for zero bytes around themselves.
+`#[endian(pad_before = N)]` writes `N` zero bytes before the field, and
+`#[endian(align = N)]` writes as many as it takes for the field to start at a
+multiple of `N` bytes from the start of the struct. Both are passed over when
+reading. `#[endian(reserved = N)]` writes `N` zero bytes after the field;
+decoding fails if they are not zero, while the `Wire` impl, which cannot fail,
+passes over them. None of these can be placed on bit fields.
+
+A `Vec<T>` or `Box<[T]>` field marked `#[endian(len = "count")]` is encoded as
+its elements, one after another, and the earlier integer field `count` holds
+how many there are; tuple structs name the field by its index, as in
+`len = "0"`. Encoding fails if `count` does not match the field's length. These
+fields have no fixed size, so they cannot appear in `#[endian(wire)]` structs.
+
+A field marked `#[endian(checksum = …)]` holds a checksum of every byte of the
+struct's wire form before it. The algorithm is `crc32`, `crc16_ccitt`,
+`internet`, or `custom(path)`, where `path` names a function from `&[u8]` to the
+field's type. Writing fills the checksum in, whatever the field holds, and
+reading fails, or panics in the `Wire` impl, if it does not match.
+
+A field marked `#[endian(magic = N)]` or `#[endian(const = EXPR)]` is always
+written as that value, and reading fails, or panics in the `Wire` impl, if it
+holds anything else. When the first field has a `magic` number and is also
+marked `detect_order`, the struct implements `endian_trait::codec::DetectOrder`
+as well, which reads the magic number in both orders to learn which one the
+rest of the struct is in.
 !*/
 
 extern crate proc_macro as pm;
@@ -57,10 +165,9 @@ extern crate syn;
 use quote::{
 	ToTokens,
 	quote,
+	quote_spanned,
 };
 
-use std::iter;
-
 use syn::{
 	Attribute,
 	Data,
@@ -68,68 +175,158 @@ use syn::{
 	DataStruct,
 	DeriveInput,
 	Fields,
-	FieldsNamed,
-	FieldsUnnamed,
 	Generics,
 	Ident,
 	Index,
+	LitStr,
+	Member,
 	Meta,
 	MetaList,
 	NestedMeta,
 	Path,
+	Type,
 	Variant,
+	Visibility,
 	spanned::Spanned,
 };
 
+mod attrs;
+mod codec;
+mod dump;
+mod layout;
+mod wire;
+
 /// Hook for receiving `#[derive(Endian)]` code
-#[proc_macro_derive(Endian)]
+#[proc_macro_derive(Endian, attributes(endian))]
 pub fn derive(source: pm::TokenStream) -> pm::TokenStream {
 	derive2(source.into()).unwrap_or_else(|err| err.to_compile_error()).into()
 }
 
+/// Hook for receiving `#[derive(Codec)]` code
+#[proc_macro_derive(Codec, attributes(endian))]
+pub fn derive_codec(source: pm::TokenStream) -> pm::TokenStream {
+	syn::parse::<DeriveInput>(source)
+		.and_then(|ast| codec::derive(&ast))
+		.unwrap_or_else(|err| err.to_compile_error())
+		.into()
+}
+
+/// Hook for receiving `#[derive(EndianDump)]` code
+#[proc_macro_derive(EndianDump, attributes(endian))]
+pub fn derive_dump(source: pm::TokenStream) -> pm::TokenStream {
+	syn::parse::<DeriveInput>(source)
+		.and_then(|ast| dump::derive(&ast))
+		.unwrap_or_else(|err| err.to_compile_error())
+		.into()
+}
+
 fn derive2(tokens: pm2::TokenStream) -> syn::Result<pm2::TokenStream> {
 	let ast = syn::parse2::<DeriveInput>(tokens)?;
 	//  Get the name of the typedef on which `Endian` is to be implemented.
 	let name = &ast.ident;
 	//  Get any generics from the typedef.
 	let generics = &ast.generics;
-	match ast.data {
+	//  Get any `#[endian(…)]` options placed on the typedef.
+	let container = attrs::Container::from_attrs(&ast.attrs)?;
+	//  Get the path at which the generated code finds this crate's library.
+	let krate = container.krate();
+	let wire = match container.wire {
+		Some(_) => wire::derive(&ast, &container)?,
+		None => pm2::TokenStream::new(),
+	};
+	let body = match ast.data {
 		//  Attempt to derive for an integer-repr enum.
-		Data::Enum(DataEnum { variants, .. }) => gen_enum(
-			name,
-			&ast.attrs,
-			variants,
-		),
-		Data::Struct(DataStruct { fields, .. }) => match fields {
-			//  Derive for a record struct
-			Fields::Named(FieldsNamed { ref named, .. }) => gen_struct(
-				name,
-				generics,
-				named.iter()
-					.map(|f| f.ident.clone().ok_or_else(|| syn::Error::new(
-						f.span(),
-						"All fields in a struct must be named",
-					)))
-					.collect::<syn::Result<Vec<_>>>()?,
-			),
-			//  Derive for a tuple struct
-			Fields::Unnamed(FieldsUnnamed { ref unnamed, .. }) => gen_struct(
+		Data::Enum(DataEnum { variants, .. }) => {
+			//  The `repr` already fixes the width of an enum, and it has no
+			//  fields to leave gaps between.
+			if container.checks_layout() {
+				return Err(syn::Error::new(
+					name.span(),
+					"`#[endian(no_padding)]`, `#[endian(from_bytes)]`, and \
+					`#[endian(size = N)]` are only supported on structs",
+				));
+			}
+			//  Reversing the bytes of an enum's discriminant usually produces
+			//  one that is not valid, which `const` evaluation rejects.
+			if let Some(span) = container.const_fn {
+				return Err(syn::Error::new(
+					span,
+					"`#[endian(const_fn)]` is only supported on structs",
+				));
+			}
+			gen_enum(name, &ast.attrs, variants, &krate)
+		},
+		Data::Struct(DataStruct { fields, .. }) => {
+			let layout = gen_layout(
 				name,
 				generics,
-				(0 .. unnamed.len()).map(|n| Index {
-					index: n as u32,
-					span: fields.span(),
-				}),
-			),
-			//  Derive for a zero-sized struct
-			Fields::Unit => gen_struct(name, generics, iter::empty::<Ident>()),
+				&container,
+				fields.iter().map(|f| &f.ty),
+			)?;
+			//  Fields marked `#[endian(as = T)]` have no `Endian` impl of their
+			//  own; only the codec converts them, through `T`. A `Duration` is
+			//  the only such field that is correct when left unconverted.
+			let mut convert = Vec::new();
+			let mut keep = Vec::new();
+			let mut checks = pm2::TokenStream::new();
+			for (member, field) in members(&fields).into_iter().zip(&fields) {
+				let opts = attrs::Field::from_field(field)?;
+				//  Only a `Wire` impl packs bit fields; the `Endian` impl would
+				//  convert them as whole fields.
+				if let (Some((span, _)), None) = (opts.bits, container.wire) {
+					return Err(syn::Error::new(
+						span,
+						"`#[derive(Endian)]` only packs bit fields in structs \
+						marked `#[endian(wire)]`",
+					));
+				}
+				match opts.as_type {
+					Some(ref ty) => {
+						if !named(&field.ty, "Duration")
+							|| !named(ty, "WireDuration")
+						{
+							return Err(syn::Error::new(
+								ty.span(),
+								"`#[derive(Endian)]` only accepts `as` on \
+								`Duration` fields, as `as = WireDuration`",
+							));
+						}
+						//  Only the name was checked above, so have the
+						//  compiler check that it is the `core` type.
+						let field_ty = &field.ty;
+						checks.extend(quote_spanned! {field_ty.span()=>
+							const _: fn(#field_ty) -> ::core::time::Duration =
+								|duration| duration;
+						});
+						keep.push(member);
+					},
+					None => convert.push((member, &field.ty)),
+				}
+			}
+			let consts = match container.const_fn {
+				Some(span) => {
+					gen_const(name, &ast.vis, generics, span, &convert, &krate)?
+				},
+				None => pm2::TokenStream::new(),
+			};
+			let body = gen_struct(name, generics, convert, keep, &krate)?;
+			Ok(quote! {
+				#body
+				#checks
+				#layout
+				#consts
+			})
 		},
-		Data::Union(..) => return Err(syn::Error::new(
+		Data::Union(..) => Err(syn::Error::new(
 			name.span(),
 			"Rust does not currently permit `#[derive(Trait)]` attributes on \
 			`union` types",
 		)),
-	}
+	}?;
+	Ok(quote! {
+		#body
+		#wire
+	})
 }
 
 /// Generate the Endian impl for an enum with an integer repr and no data body.
@@ -137,7 +334,57 @@ fn gen_enum<'a>(
 	name: &Ident,
 	attrs: impl IntoIterator<Item = &'a Attribute>,
 	variants: impl IntoIterator<Item = Variant>,
+	krate: &Path,
 ) -> syn::Result<pm2::TokenStream> {
+	let kind = enum_repr(name, attrs, variants, "Endian")?;
+	/* To implement `Endian`, each function casts a pointer to `Self` into a
+	pointer to the `repr` type, then runs the `Endian` function on the
+	pointed-to value, and stores the transformed value back in the `self` slot.
+	This introduces undefined behavior, in that the enum now contains a value
+	not enumerated in the symbol list; however, in practice, this appears to not
+	matter as long as the enum value is not used as its own type until
+	transformed back into a valid variant.
+
+	The expression `enum_value.to_be().from_be()` operates correctly as of 1.31.
+	*/
+	Ok(quote! {
+		impl #krate::Endian for #name {
+			fn from_be(mut self) -> Self {
+				let ptr = &mut self as *mut Self as *mut #kind;
+				unsafe { ptr.write(#krate::Endian::from_be(ptr.read())) }
+				self
+			}
+			fn from_le(mut self) -> Self {
+				let ptr = &mut self as *mut Self as *mut #kind;
+				unsafe { ptr.write(#krate::Endian::from_le(ptr.read())) }
+				self
+			}
+			fn to_be(mut self) -> Self {
+				let ptr = &mut self as *mut Self as *mut #kind;
+				unsafe { ptr.write(#krate::Endian::to_be(ptr.read())) }
+				self
+			}
+			fn to_le(mut self) -> Self {
+				let ptr = &mut self as *mut Self as *mut #kind;
+				unsafe { ptr.write(#krate::Endian::to_le(ptr.read())) }
+				self
+			}
+		}
+
+		//  A data-less enum is exactly its integer discriminant.
+		unsafe impl #krate::bytes::AsBytes for #name {}
+	})
+}
+
+/// Find the integer `repr` of an enum with no data body.
+///
+/// `derive` names the trait being derived, for the error messages.
+fn enum_repr<'a, V: std::borrow::Borrow<Variant>>(
+	name: &Ident,
+	attrs: impl IntoIterator<Item = &'a Attribute>,
+	variants: impl IntoIterator<Item = V>,
+	derive: &str,
+) -> syn::Result<Ident> {
 	/* There is only one valid path through the syntax tree for working with
 	enums. Rather than drift rightward at each layer, the processing code
 	produces a `syn::Result` at each step, and bubbles errors.
@@ -155,8 +402,11 @@ fn gen_enum<'a>(
 		.ident == "repr"
 	).ok_or_else(|| syn::Error::new(
 		name.span(),
-		"`#[derive(Endian)]` requires a `#[repr]` of `C` or some integer for \
-		enums",
+		format!(
+			"`#[derive({})]` requires a `#[repr]` of `C` or some integer for \
+			enums",
+			derive,
+		),
 	))?
 	//  Once a `#[repr]` attribute is discovered, parse it as an attribute and
 	//  query its internals.
@@ -199,8 +449,11 @@ fn gen_enum<'a>(
 		//  not supported.
 		kind if kind == "C" || kind == "packed" => Err(syn::Error::new(
 			kind.span(),
-			"`#[repr(C)]` and `#[repr(packed)]` enums cannot currently \
-			implement `Endian`",
+			format!(
+				"`#[repr(C)]` and `#[repr(packed)]` enums cannot currently \
+				implement `{}`",
+				derive,
+			),
 		)),
 		//  Test if the representation is a Rust fundamental integer.
 		kind if kind == "i8" || kind == "i16" || kind == "i32" || kind == "i64"
@@ -208,50 +461,19 @@ fn gen_enum<'a>(
 		|| kind == "i128" || kind == "u128" => {
 			//  Check that each variant of the enum has no data.
 			for var in variants {
+				let var = var.borrow();
 				if let Fields::Unit = var.fields {}
 				else {
 					return Err(syn::Error::new(
 						var.fields.span(),
-						"`Endian` cannot be derived on enums with data fields",
+						format!(
+							"`{}` cannot be derived on enums with data fields",
+							derive,
+						),
 					));
 				}
 			}
-			/* To implement `Endian`, each function casts a pointer to `Self`
-			into a pointer to the `repr` type, then runs the `Endian` function
-			on the pointed-to value, and stores the transformed value back in
-			the `self` slot. This introduces undefined behavior, in that the
-			enum now contains a value not enumerated in the symbol list;
-			however, in practice, this appears to not matter as long as the enum
-			value is not used as its own type until transformed back into a
-			valid variant.
-
-			The expression `enum_value.to_be().from_be()` operates correctly as
-			of 1.31.
-			*/
-			Ok(quote! {
-				impl Endian for #name {
-					fn from_be(mut self) -> Self {
-						let ptr = &mut self as *mut Self as *mut #kind;
-						unsafe { ptr.write(Endian::from_be(ptr.read())) }
-						self
-					}
-					fn from_le(mut self) -> Self {
-						let ptr = &mut self as *mut Self as *mut #kind;
-						unsafe { ptr.write(Endian::from_le(ptr.read())) }
-						self
-					}
-					fn to_be(mut self) -> Self {
-						let ptr = &mut self as *mut Self as *mut #kind;
-						unsafe { ptr.write(Endian::to_be(ptr.read())) }
-						self
-					}
-					fn to_le(mut self) -> Self {
-						let ptr = &mut self as *mut Self as *mut #kind;
-						unsafe { ptr.write(Endian::to_le(ptr.read())) }
-						self
-					}
-				}
-			})
+			Ok(kind.clone())
 		},
 		kind => Err(syn::Error::new(
 			kind.span(),
@@ -262,18 +484,37 @@ fn gen_enum<'a>(
 }
 
 /// Generate the Endian impl for a struct type.
-fn gen_struct(
+///
+/// The `fields` are converted, and the `kept` fields are moved into the result
+/// unchanged.
+fn gen_struct<'a>(
 	name: &Ident,
 	generics: &Generics,
-	fields: impl IntoIterator<Item = impl Clone + ToTokens>,
+	fields: impl IntoIterator<Item = (impl Clone + ToTokens, &'a Type)>,
+	kept: impl IntoIterator<Item = impl Clone + ToTokens>,
+	krate: &Path,
 ) -> syn::Result<pm2::TokenStream> {
 	/* Due to how `quote!` handles iterators, the sequence of field names must
 	be collected into a `Vec` and then cloned, so that each name can be used
 	twice in the `quote!` body. Otherwise, `quote!` would advance the iterator
 	twice per expansion, which is very incorrect.
 	*/
-	let l = fields.into_iter().collect::<Vec<_>>();
+	let (l, types): (Vec<_>, Vec<_>) = fields.into_iter().unzip();
 	let r = l.clone();
+	let k = kept.into_iter().collect::<Vec<_>>();
+	/* Each converted field's type is asserted to be `Endian` in the impl's
+	`where` clause, with the span of that type. A field that is not `Endian`
+	then fails its own bound, and the error points at the field and names its
+	type, rather than pointing at the derive once for every conversion of it.
+	*/
+	let mut generics = generics.clone();
+	let preds = generics.make_where_clause();
+	for ty in types {
+		let krate = krate_at(krate, ty.span());
+		preds.predicates.push(syn::parse2(quote_spanned! {ty.span()=>
+			#ty: #krate::Endian
+		})?);
+	}
 	//  Generics must be split into appropriate forms for the impl block.
 	let (g_impl, g_ty, g_where) = generics.split_for_impl();
 	/* Structs are recursively Endian: the conversion is just a conversion of
@@ -286,27 +527,247 @@ fn gen_struct(
 	structs can be written as `Name {}` (empty records).
 	*/
 	Ok(quote! {
-		impl #g_impl Endian for #name #g_ty #g_where {
+		impl #g_impl #krate::Endian for #name #g_ty #g_where {
 			fn from_be(self) -> Self {
 				Self {
-					#( #l: Endian::from_be(self.#r), )*
+					#( #l: #krate::Endian::from_be(self.#r), )*
+					#( #k: self.#k, )*
 				}
 			}
 			fn from_le(self) -> Self {
 				Self {
-					#( #l: Endian::from_le(self.#r), )*
+					#( #l: #krate::Endian::from_le(self.#r), )*
+					#( #k: self.#k, )*
 				}
 			}
 			fn to_be(self) -> Self {
 				Self {
-					#( #l: Endian::to_be(self.#r), )*
+					#( #l: #krate::Endian::to_be(self.#r), )*
+					#( #k: self.#k, )*
 				}
 			}
 			fn to_le(self) -> Self {
 				Self {
-					#( #l: Endian::to_le(self.#r), )*
+					#( #l: #krate::Endian::to_le(self.#r), )*
+					#( #k: self.#k, )*
 				}
 			}
 		}
 	})
 }
+
+/// Generate compile-time assertions about the layout of a struct type.
+///
+/// `#[endian(no_padding)]` asserts that the struct is exactly as wide as the sum
+/// of its fields, which means that transmuting it into a byte array cannot read
+/// uninitialized padding. `#[endian(size = N)]` asserts that it is exactly `N`
+/// bytes wide. Either failure is reported when the crate defining the struct is
+/// compiled, rather than when its bytes are put on the wire.
+///
+/// A struct with no padding is also marked `AsBytes`, and one marked
+/// `#[endian(from_bytes)]` is also marked `FromBytes`. Each marker requires the
+/// same of every field.
+fn gen_layout<'a>(
+	name: &Ident,
+	generics: &Generics,
+	container: &attrs::Container,
+	types: impl IntoIterator<Item = &'a Type>,
+) -> syn::Result<pm2::TokenStream> {
+	if !container.checks_layout() {
+		return Ok(pm2::TokenStream::new());
+	}
+	let krate = container.krate();
+	/* The assertions are `const` items, which cannot name the parameters of a
+	generic type. A check inside the impl would only run for the instantiations
+	that happen to be used, so refuse generic types outright rather than check
+	them partially.
+	*/
+	if !generics.params.is_empty() {
+		return Err(syn::Error::new(
+			generics.span(),
+			"`#[endian(no_padding)]`, `#[endian(from_bytes)]`, and \
+			`#[endian(size = N)]` cannot be checked on generic types",
+		));
+	}
+	let types = types.into_iter().collect::<Vec<_>>();
+	let mut out = pm2::TokenStream::new();
+	if let Some(span) = container.padding_free() {
+		//  Sum the field widths without a leading `0 +`, which lints as a
+		//  no-op in the user's crate.
+		let sum = match types.split_first() {
+			Some((first, rest)) => quote! {
+				::core::mem::size_of::<#first>()
+					#( + ::core::mem::size_of::<#rest>() )*
+			},
+			None => quote!(0),
+		};
+		let msg = LitStr::new(
+			&format!("`{}` contains padding bytes", name),
+			span,
+		);
+		out.extend(quote_spanned! {span=>
+			const _: () = ::core::assert!(
+				::core::mem::size_of::<#name>() == #sum,
+				#msg,
+			);
+		});
+		let bounds = types.iter().map(|ty| {
+			let krate = krate_at(&krate, ty.span());
+			quote_spanned! {ty.span()=>
+				#ty: #krate::bytes::AsBytes
+			}
+		});
+		out.extend(quote! {
+			unsafe impl #krate::bytes::AsBytes for #name
+			where #( #bounds, )* {}
+		});
+	}
+	if container.from_bytes.is_some() {
+		let bounds = types.iter().map(|ty| {
+			let krate = krate_at(&krate, ty.span());
+			quote_spanned! {ty.span()=>
+				#ty: #krate::bytes::FromBytes
+			}
+		});
+		out.extend(quote! {
+			unsafe impl #krate::bytes::FromBytes for #name
+			where #( #bounds, )* {}
+		});
+	}
+	if let Some(ref size) = container.size {
+		let msg = LitStr::new(
+			&format!("`{}` is not {} bytes wide", name, size),
+			size.span(),
+		);
+		out.extend(quote_spanned! {size.span()=>
+			const _: () = ::core::assert!(
+				::core::mem::size_of::<#name>() == #size,
+				#msg,
+			);
+		});
+	}
+	Ok(out)
+}
+
+/// Generate the ConstEndian impl for a struct type, and its `const fn`
+/// conversions.
+///
+/// The struct's byte ranges are those of each of the converted `fields`, moved
+/// to the field's offset. Fields that are kept unchanged have no ranges.
+fn gen_const(
+	name: &Ident,
+	vis: &Visibility,
+	generics: &Generics,
+	span: pm2::Span,
+	fields: &[(Member, &Type)],
+	krate: &Path,
+) -> syn::Result<pm2::TokenStream> {
+	//  The number of ranges is an array length, which cannot depend on the
+	//  parameters of a generic type.
+	if !generics.params.is_empty() {
+		return Err(syn::Error::new(
+			generics.span(),
+			"`#[endian(const_fn)]` cannot be placed on generic types",
+		));
+	}
+	let bounds = fields.iter().map(|(_, ty)| {
+		let krate = krate_at(krate, ty.span());
+		quote_spanned! {ty.span()=>
+			#ty: #krate::ConstEndian
+		}
+	}).collect::<Vec<_>>();
+	let members = fields.iter().map(|(member, _)| member).collect::<Vec<_>>();
+	let swaps = fields.iter()
+		.map(|(_, ty)| quote!(<#ty as #krate::ConstEndian>::SWAPS))
+		.collect::<Vec<_>>();
+	Ok(quote_spanned! {span=>
+		unsafe impl #krate::ConstEndian for #name
+		where #( #bounds, )* {
+			const SWAPS: &'static [(usize, usize)] = &#krate::consts::merge::<{
+				#krate::consts::count(&[ #( #swaps, )* ])
+			}>(&[ #( (::core::mem::offset_of!(Self, #members), #swaps), )* ]);
+		}
+
+		//  The methods take the struct's own visibility, and a private struct
+		//  need not use all of them. They are named after the `Endian`
+		//  methods, which take `self` whatever their names suggest.
+		#[allow(dead_code, clippy::wrong_self_convention)]
+		impl #name
+		where #( #bounds, )* {
+			/// Converts from host endian to big-endian order in a `const`
+			/// context.
+			#vis const fn to_be_const(self) -> Self {
+				#krate::const_be(self)
+			}
+
+			/// Converts from host endian to little-endian order in a `const`
+			/// context.
+			#vis const fn to_le_const(self) -> Self {
+				#krate::const_le(self)
+			}
+
+			/// Converts from big-endian order to host endian in a `const`
+			/// context.
+			#vis const fn from_be_const(self) -> Self {
+				#krate::const_be(self)
+			}
+
+			/// Converts from little-endian order to host endian in a `const`
+			/// context.
+			#vis const fn from_le_const(self) -> Self {
+				#krate::const_le(self)
+			}
+		}
+	})
+}
+
+/// Tests whether a type is a path that ends in `name`.
+fn named(ty: &Type, name: &str) -> bool {
+	match ty {
+		Type::Path(path) => path.path
+			.segments
+			.last()
+			.is_some_and(|seg| seg.ident == name && seg.arguments.is_empty()),
+		_ => false,
+	}
+}
+
+/// Name each field of a struct, as it would appear in `self.field` or in a
+/// `Self { field: … }` expression.
+fn members(fields: &Fields) -> Vec<Member> {
+	fields.iter()
+		.enumerate()
+		.map(|(n, f)| match f.ident {
+			Some(ref ident) => Member::Named(ident.clone()),
+			None => Member::Unnamed(Index {
+				index: n as u32,
+				span: f.span(),
+			}),
+		})
+		.collect()
+}
+
+/// Give every token of the crate path the span of a field, so that a bound
+/// naming the crate is reported at that field rather than at the derive.
+///
+/// The compiler reports an unsatisfied bound at its trait, so it is not enough
+/// for only the bounded type to carry the field's span.
+fn krate_at(krate: &Path, span: pm2::Span) -> pm2::TokenStream {
+	fn respan(tokens: pm2::TokenStream, span: pm2::Span) -> pm2::TokenStream {
+		tokens.into_iter()
+			.map(|tt| match tt {
+				pm2::TokenTree::Group(group) => {
+					let stream = respan(group.stream(), span);
+					let mut group = pm2::Group::new(group.delimiter(), stream);
+					group.set_span(span);
+					group.into()
+				},
+				mut tt => {
+					tt.set_span(span);
+					tt
+				},
+			})
+			.collect()
+	}
+	respan(krate.to_token_stream(), span)
+}
@@ -0,0 +1,303 @@
+/*! Code generation for `#[endian(wire)]`
+
+A type marked `#[endian(wire)]` implements `Wire` as well as `Endian`. Structs
+write each of their fields in declaration order, with nothing between them but
+the padding their options ask for, as laid out by the `layout` module. Reserved
+bytes are not checked when reading. Enums write their `repr` integer, and
+reading panics on integers that are not one of the enum's discriminants.
+!*/
+
+use crate::attrs::{
+	BitOrder,
+	Container,
+};
+
+use crate::layout::{
+	self,
+	Backend,
+	Item,
+};
+
+use pm2::{
+	Literal,
+	TokenStream,
+};
+
+use quote::{
+	ToTokens,
+	quote,
+	quote_spanned,
+};
+
+use syn::{
+	Data,
+	DataEnum,
+	DataStruct,
+	DeriveInput,
+	Fields,
+	Ident,
+	Path,
+	spanned::Spanned,
+};
+
+/// Moves values through byte slices, with `Wire`.
+struct Slices {
+	krate: Path,
+}
+
+impl Backend for Slices {
+	fn krate(&self) -> &Path {
+		&self.krate
+	}
+
+	fn put(&self, value: TokenStream) -> TokenStream {
+		let krate = &self.krate;
+		quote! {
+			#krate::wire::put(#value, order, &mut buf);
+		}
+	}
+
+	fn take(&self, ty: Option<&dyn ToTokens>) -> TokenStream {
+		let krate = &self.krate;
+		match ty {
+			Some(ty) => quote! {
+				#krate::wire::take::<#ty>(order, &mut buf)
+			},
+			None => quote! {
+				#krate::wire::take(order, &mut buf)
+			},
+		}
+	}
+
+	fn put_failed(&self, msg: &str) -> TokenStream {
+		quote!(::core::panic!("{}", #msg))
+	}
+
+	fn take_failed(&self, msg: &str) -> TokenStream {
+		quote!(::core::panic!("{}", #msg))
+	}
+
+	fn pad(&self, len: TokenStream) -> TokenStream {
+		let krate = &self.krate;
+		quote! {
+			#krate::wire::pad(#len, &mut buf);
+		}
+	}
+
+	//  Reading a wire form cannot fail without a panic, so reserved bytes are
+	//  passed over rather than checked.
+	fn skip(&self, len: TokenStream, _: Option<&str>) -> TokenStream {
+		let krate = &self.krate;
+		quote! {
+			#krate::wire::skip(#len, &mut buf);
+		}
+	}
+
+	//  Wire layouts have a fixed size, so `derive` rejects sequences before
+	//  they get here.
+	fn put_seq(&self, _: TokenStream) -> TokenStream {
+		unreachable!("sequences have no fixed size")
+	}
+
+	fn take_seq(&self, _: TokenStream) -> TokenStream {
+		unreachable!("sequences have no fixed size")
+	}
+
+	//  Checksums look back over the buffer, so it is kept whole as `__whole`,
+	//  and written through a reborrow.
+	fn track_write(&self, record: bool) -> TokenStream {
+		let whole = if record {
+			quote! {
+				let __whole: &mut [u8] = buf;
+				let mut buf = &mut *__whole;
+			}
+		}
+		else {
+			TokenStream::new()
+		};
+		quote! {
+			#whole
+			let __total = buf.len();
+		}
+	}
+
+	fn track_read(&self, _: bool) -> TokenStream {
+		quote! {
+			let __whole: &[u8] = buf;
+			let __total = buf.len();
+		}
+	}
+
+	fn written(&self) -> TokenStream {
+		quote!((__total - buf.len()))
+	}
+
+	fn been_read(&self) -> TokenStream {
+		self.written()
+	}
+
+	fn read_bytes(&self) -> TokenStream {
+		quote!(&__whole[.. __total - buf.len()])
+	}
+
+	//  The bytes before the checksum cannot be read back while the buffer is
+	//  being split up to write the rest, so its place is held with zeros and
+	//  filled in at the end.
+	fn put_checksum(
+		&self,
+		ty: &TokenStream,
+		func: &Path,
+		slot: &Ident,
+	) -> (TokenStream, TokenStream) {
+		let krate = &self.krate;
+		let size = quote!(<#ty as #krate::wire::Wire>::WIRE_SIZE);
+		let pad = self.pad(size);
+		(
+			quote! {
+				let #slot = __total - buf.len();
+				#pad
+			},
+			quote! {
+				let __sum: #ty = #func(&__whole[.. #slot]);
+				#krate::wire::Wire::write_wire(
+					&__sum,
+					order,
+					&mut __whole[#slot ..],
+				);
+			},
+		)
+	}
+
+}
+
+/// Generate the Wire impl for a struct or data-less enum.
+pub fn derive(
+	ast: &DeriveInput,
+	container: &Container,
+) -> syn::Result<TokenStream> {
+	let name = &ast.ident;
+	let krate = container.krate();
+	let mut generics = ast.generics.clone();
+	let (size, write, read) = match ast.data {
+		Data::Enum(DataEnum { ref variants, .. }) => {
+			let kind = super::enum_repr(name, &ast.attrs, variants, "Endian")?;
+			let vars = variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+			let msg = format!("{{:X}} is not a discriminant of `{}`", name);
+			(
+				quote!(::core::mem::size_of::<#kind>()),
+				quote! {
+					let repr: #kind = match *self {
+						#( Self::#vars => Self::#vars as #kind, )*
+					};
+					#krate::wire::Wire::write_wire(&repr, order, buf)
+				},
+				quote! {
+					let repr: #kind =
+						#krate::wire::Wire::read_wire(order, buf);
+					#( if repr == Self::#vars as #kind {
+						return Self::#vars;
+					} )*
+					::core::panic!(#msg, repr)
+				},
+			)
+		},
+		Data::Struct(DataStruct { ref fields, .. }) => {
+			let items = layout::plan(fields, &krate)?;
+			for item in &items {
+				if let Item::Seq(n, _) = *item {
+					let field = fields.iter()
+						.nth(n)
+						.expect("items only refer to fields that exist");
+					return Err(syn::Error::new(
+						field.span(),
+						"`len` fields have no fixed size, so they cannot be \
+						part of a wire layout",
+					));
+				}
+			}
+			let types = items.iter()
+				.filter_map(|item| item.wire_type(fields))
+				.collect::<Vec<_>>();
+			let preds = generics.make_where_clause();
+			for ty in &types {
+				let krate = super::krate_at(&krate, ty.span());
+				preds.predicates.push(syn::parse2(quote_spanned! {ty.span()=>
+					#ty: #krate::wire::Wire
+				})?);
+			}
+			let size = wire_size(fields, &items, &krate);
+			let (write, read) = layout::gen_struct(
+				fields,
+				&items,
+				container.bit_order.unwrap_or(BitOrder::Msb),
+				&Slices { krate: krate.clone() },
+			)?;
+			(size, write, read)
+		},
+		Data::Union(..) => return Err(syn::Error::new(
+			name.span(),
+			"Rust does not currently permit `#[derive(Trait)]` attributes on \
+			`union` types",
+		)),
+	};
+	let (g_impl, g_ty, g_where) = generics.split_for_impl();
+	Ok(quote! {
+		impl #g_impl #krate::wire::Wire for #name #g_ty #g_where {
+			const WIRE_SIZE: usize = #size;
+
+			#[allow(unused_mut, unused_variables)]
+			fn write_wire(
+				&self,
+				order: #krate::Endianness,
+				mut buf: &mut [u8],
+			) {
+				#write
+			}
+
+			#[allow(unused_mut, unused_variables)]
+			fn read_wire(
+				order: #krate::Endianness,
+				mut buf: &[u8],
+			) -> Self {
+				#read
+			}
+		}
+	})
+}
+
+/// Build the constant expression for the size of a struct's wire form.
+///
+/// Alignment depends on everything before it, so the sum is built up one item
+/// at a time, rounding up at each `align` option.
+fn wire_size(fields: &Fields, items: &[Item], krate: &Path) -> TokenStream {
+	let mut size = None::<TokenStream>;
+	for item in items {
+		let this = match *item {
+			Item::Pad(len) | Item::Reserved(len) => {
+				Literal::usize_unsuffixed(len).into_token_stream()
+			},
+			Item::Align(align) => {
+				let prev = size.take().unwrap_or_else(|| quote!(0));
+				let align = Literal::usize_unsuffixed(align);
+				size = Some(quote! {
+					::core::primitive::usize::div_ceil(#prev, #align) * #align
+				});
+				continue;
+			},
+			Item::Whole(..)
+			| Item::Bits(_)
+			| Item::Seq(..)
+			| Item::Checksum(..)
+			| Item::Const(..) => {
+				let ty = item.wire_type(fields)
+					.expect("fields have a wire type");
+				quote!(<#ty as #krate::wire::Wire>::WIRE_SIZE)
+			},
+		};
+		size = Some(match size.take() {
+			Some(prev) => quote!(#prev + #this),
+			None => this,
+		});
+	}
+	size.unwrap_or_else(|| quote!(0))
+}
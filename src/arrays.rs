@@ -1,68 +1,63 @@
 /*! Implement `Endian` on standard arrays.
 
 I want this library to remain zero-allocation, so I can't return a new Vec that
-has executed the conversion, and without type-level integers I can't accept
-arbitrary arrays.
+has executed the conversion. Arrays of any length, however, can be converted by
+value now that the language has const generics.
 
-This mutates an array in place, replacing each element with its converted form.
+This converts each element of an array by value, and collects the converted
+elements into an array of the same length.
 !*/
 
 use super::Endian;
-use std::ptr;
 
-//  Implement on specific array length types
-macro_rules! implendian_a {
-	( $( $n:expr, )* ) => { $(
-		impl<T: Endian> Endian for [T; $n] {
-			fn from_be(mut self) -> Self {
-				for el in self.iter_mut() { unsafe {
-					ptr::write(el, ptr::read(el).from_be());
-				} }
-				self
-			}
-			fn from_le(mut self) -> Self {
-				for el in self.iter_mut() { unsafe {
-					ptr::write(el, ptr::read(el).from_le());
-				} }
-				self
-			}
-			fn to_be(mut self) -> Self {
-				for el in self.iter_mut() { unsafe {
-					ptr::write(el, ptr::read(el).to_be());
-				} }
-				self
-			}
-			fn to_le(mut self) -> Self {
-				for el in self.iter_mut() { unsafe {
-					ptr::write(el, ptr::read(el).to_le());
-				} }
-				self
-			}
-		}
-	)* };
+impl<T: Endian, const N: usize> Endian for [T; N] {
+	fn from_be(self) -> Self {
+		self.map(T::from_be)
+	}
+	fn from_le(self) -> Self {
+		self.map(T::from_le)
+	}
+	fn to_be(self) -> Self {
+		self.map(T::to_be)
+	}
+	fn to_le(self) -> Self {
+		self.map(T::to_le)
+	}
 }
 
-implendian_a![
-	0,
-	1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16,
-	17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32,
-];
-
 #[cfg(test)]
 mod tests {
-	use Endian;
+	use crate::Endian;
 
 	#[test]
 	fn arrays() {
 		let src: [i32; 8] = [
 			1, 2, 3, 4, 5, 6, 7, 8,
 		];
-		let flip: [i32; 8] = src.clone().to_be();
-		let mut comp: [i32; 8] = src.clone();
+		let flip: [i32; 8] = src.to_be();
+		let mut comp: [i32; 8] = src;
 		for e in comp.iter_mut() {
 			*e = e.to_be();
 		}
 
 		assert_eq!(flip, comp);
 	}
+
+	//  A conversion that panics unwinds out of the array, rather than aborting.
+	#[test]
+	fn panicking_element() {
+		extern crate std;
+
+		struct Fails;
+
+		impl Endian for Fails {
+			fn from_be(self) -> Self { panic!("cannot convert") }
+			fn from_le(self) -> Self { panic!("cannot convert") }
+			fn to_be(self) -> Self { panic!("cannot convert") }
+			fn to_le(self) -> Self { panic!("cannot convert") }
+		}
+
+		let res = std::panic::catch_unwind(|| [Fails, Fails].to_be());
+		assert!(res.is_err());
+	}
 }
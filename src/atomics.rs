@@ -0,0 +1,120 @@
+/*! Implement `Endian` on the atomic integers.
+
+An atomic integer can be converted in two ways. Owned, and through `&mut`, no
+other thread can observe it, so the conversion is a plain read and write of the
+inner value; these are the `Endian` implementations, and they allow structs with
+atomic fields to derive `Endian`.
+
+A shared atomic is usually stored in a fixed order so that another process,
+perhaps on a machine of different endianness, can read it from shared memory.
+The `AtomicEndian` trait provides loads and stores that convert between that
+order and the host's as part of the atomic operation.
+!*/
+
+use super::Endian;
+
+use core::sync::atomic::{
+	self,
+	Ordering,
+};
+
+/** Atomic loads and stores of values kept in a fixed byte order.
+
+```rust
+use core::sync::atomic::{AtomicU32, Ordering};
+use endian_trait::AtomicEndian;
+
+let head = AtomicU32::new(0);
+head.store_be(0x0102_0304, Ordering::Release);
+assert_eq!(head.load(Ordering::Relaxed).to_ne_bytes(), [1, 2, 3, 4]);
+assert_eq!(head.load_be(Ordering::Acquire), 0x0102_0304);
+```
+**/
+pub trait AtomicEndian {
+	/// The integer that the atomic holds.
+	type Value: Endian;
+
+	/// Loads a big-endian value, and converts it to host endian.
+	fn load_be(&self, order: Ordering) -> Self::Value;
+
+	/// Loads a little-endian value, and converts it to host endian.
+	fn load_le(&self, order: Ordering) -> Self::Value;
+
+	/// Converts a value to big-endian, and stores it.
+	fn store_be(&self, value: Self::Value, order: Ordering);
+
+	/// Converts a value to little-endian, and stores it.
+	fn store_le(&self, value: Self::Value, order: Ordering);
+}
+
+/// Implement Endian, owned and through `&mut`, and AtomicEndian, on the atomic
+/// integers of each width that the target supports.
+macro_rules! implendian_a {
+	( $( $w:tt => $( $a:ident : $t:ty ),* ; )* ) => { $( $(
+		#[cfg(target_has_atomic = $w)]
+		impl Endian for atomic::$a {
+			fn from_be(self) -> Self {
+				Self::new(self.into_inner().from_be())
+			}
+			fn from_le(self) -> Self {
+				Self::new(self.into_inner().from_le())
+			}
+			fn to_be(self) -> Self {
+				Self::new(self.into_inner().to_be())
+			}
+			fn to_le(self) -> Self {
+				Self::new(self.into_inner().to_le())
+			}
+		}
+
+		#[cfg(target_has_atomic = $w)]
+		impl Endian for &mut atomic::$a {
+			fn from_be(self) -> Self {
+				let val = self.get_mut();
+				*val = val.from_be();
+				self
+			}
+			fn from_le(self) -> Self {
+				let val = self.get_mut();
+				*val = val.from_le();
+				self
+			}
+			fn to_be(self) -> Self {
+				let val = self.get_mut();
+				*val = val.to_be();
+				self
+			}
+			fn to_le(self) -> Self {
+				let val = self.get_mut();
+				*val = val.to_le();
+				self
+			}
+		}
+
+		#[cfg(target_has_atomic = $w)]
+		impl AtomicEndian for atomic::$a {
+			type Value = $t;
+
+			fn load_be(&self, order: Ordering) -> $t {
+				self.load(order).from_be()
+			}
+			fn load_le(&self, order: Ordering) -> $t {
+				self.load(order).from_le()
+			}
+			fn store_be(&self, value: $t, order: Ordering) {
+				self.store(value.to_be(), order)
+			}
+			fn store_le(&self, value: $t, order: Ordering) {
+				self.store(value.to_le(), order)
+			}
+		}
+	)* )* };
+}
+
+implendian_a! {
+	"8" => AtomicI8: i8, AtomicU8: u8;
+	"16" => AtomicI16: i16, AtomicU16: u16;
+	"32" => AtomicI32: i32, AtomicU32: u32;
+	"64" => AtomicI64: i64, AtomicU64: u64;
+	"ptr" => AtomicIsize: isize, AtomicUsize: usize;
+}
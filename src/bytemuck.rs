@@ -0,0 +1,95 @@
+/*! Interoperation with `bytemuck`
+
+`bytemuck::Pod` types already know how to be viewed as bytes, but not which
+order those bytes are in. These functions cast between byte buffers and `Pod`
+values, converting each value as it passes through, so that a `Pod` type which
+also derives `Endian` can be read from and written to the wire directly.
+
+Input buffers need not be aligned, since every value is copied. The functions
+panic when the buffer is not a whole number of values, in the same manner as
+`bytemuck::cast_slice`; the `try_` versions return an error instead.
+!*/
+
+use super::Endian;
+
+use alloc::vec::Vec;
+
+use ::bytemuck::{
+	Pod,
+	PodCastError,
+};
+
+use core::mem;
+
+/// Copies big-endian values out of a byte buffer, converting each to local
+/// order.
+///
+/// # Panics
+///
+/// This panics if the buffer's length is not a multiple of the width of `T`.
+pub fn cast_slice_from_be<T: Pod + Endian>(bytes: &[u8]) -> Vec<T> {
+	unwrap("cast_slice_from_be", try_cast_slice_from_be(bytes))
+}
+
+/// Copies little-endian values out of a byte buffer, converting each to local
+/// order.
+///
+/// # Panics
+///
+/// This panics if the buffer's length is not a multiple of the width of `T`.
+pub fn cast_slice_from_le<T: Pod + Endian>(bytes: &[u8]) -> Vec<T> {
+	unwrap("cast_slice_from_le", try_cast_slice_from_le(bytes))
+}
+
+/// Copies big-endian values out of a byte buffer, converting each to local
+/// order, or fails if the buffer's length is not a multiple of the width of
+/// `T`.
+pub fn try_cast_slice_from_be<T: Pod + Endian>(
+	bytes: &[u8],
+) -> Result<Vec<T>, PodCastError> {
+	cast_from(bytes, Endian::from_be)
+}
+
+/// Copies little-endian values out of a byte buffer, converting each to local
+/// order, or fails if the buffer's length is not a multiple of the width of
+/// `T`.
+pub fn try_cast_slice_from_le<T: Pod + Endian>(
+	bytes: &[u8],
+) -> Result<Vec<T>, PodCastError> {
+	cast_from(bytes, Endian::from_le)
+}
+
+/// Converts local values to big-endian, and collects their bytes.
+pub fn cast_slice_to_be<T: Pod + Endian>(values: &[T]) -> Vec<u8> {
+	cast_to(values, Endian::to_be)
+}
+
+/// Converts local values to little-endian, and collects their bytes.
+pub fn cast_slice_to_le<T: Pod + Endian>(values: &[T]) -> Vec<u8> {
+	cast_to(values, Endian::to_le)
+}
+
+fn cast_from<T: Pod>(
+	bytes: &[u8],
+	convert: fn(T) -> T,
+) -> Result<Vec<T>, PodCastError> {
+	let size = mem::size_of::<T>();
+	if size == 0 || bytes.len() % size != 0 {
+		return Err(PodCastError::OutputSliceWouldHaveSlop);
+	}
+	bytes.chunks_exact(size)
+		.map(|chunk| ::bytemuck::try_pod_read_unaligned(chunk).map(convert))
+		.collect()
+}
+
+fn cast_to<T: Pod>(values: &[T], convert: fn(T) -> T) -> Vec<u8> {
+	let mut out = Vec::with_capacity(mem::size_of_val(values));
+	for &value in values {
+		out.extend_from_slice(::bytemuck::bytes_of(&convert(value)));
+	}
+	out
+}
+
+fn unwrap<T>(func: &str, res: Result<T, PodCastError>) -> T {
+	res.unwrap_or_else(|err| panic!("{}>{:?}", func, err))
+}
@@ -0,0 +1,100 @@
+/*! Interoperation with `byteorder`
+
+`byteorder` names byte orders as types, `BigEndian` and `LittleEndian`, and
+selects them with an `O: ByteOrder` type parameter. `EndianOrder` connects those
+types to the `Endian` methods: `BigEndian` performs `to_be`/`from_be`, and
+`LittleEndian` performs `to_le`/`from_le`. This allows code that is generic over
+`O` to convert `Endian` types in the same order that it reads primitives with
+`ReadBytesExt`.
+
+`ReadEndianExt` and `WriteEndianExt` then read and write whole `Endian` values,
+such as derived structs marked `#[endian(from_bytes)]`, on any stream:
+
+```rust
+use byteorder::{BigEndian, ReadBytesExt};
+use endian_trait::Endian;
+use endian_trait::byteorder::ReadEndianExt;
+
+#[repr(C)]
+#[derive(Clone, Copy, Endian)]
+#[endian(from_bytes)]
+struct Header {
+    kind: u16,
+    length: u16,
+}
+
+let mut wire = &[0, 1, 0, 8, 0xde, 0xad][..];
+let header: Header = wire.read_endian::<_, BigEndian>().unwrap();
+let body = wire.read_u16::<BigEndian>().unwrap();
+assert_eq!((header.kind, header.length, body), (1, 8, 0xdead));
+```
+!*/
+
+use super::{
+	Endian,
+	Endianness,
+};
+
+use super::bytes::{
+	AsBytes,
+	FromBytes,
+};
+
+use ::byteorder::{
+	BigEndian,
+	ByteOrder,
+	LittleEndian,
+	ReadBytesExt,
+	WriteBytesExt,
+};
+
+use std::io;
+
+/// Maps a `byteorder` byte order onto the matching `Endian` methods.
+pub trait EndianOrder: ByteOrder {
+	/// The byte order this type names.
+	const ENDIANNESS: Endianness;
+
+	/// Converts a value from host endian to this order.
+	fn to_order<T: Endian>(value: T) -> T {
+		Self::ENDIANNESS.convert_to(value)
+	}
+
+	/// Converts a value from this order to host endian.
+	fn from_order<T: Endian>(value: T) -> T {
+		Self::ENDIANNESS.convert_from(value)
+	}
+}
+
+impl EndianOrder for BigEndian {
+	const ENDIANNESS: Endianness = Endianness::Big;
+}
+
+impl EndianOrder for LittleEndian {
+	const ENDIANNESS: Endianness = Endianness::Little;
+}
+
+/// Reads `Endian` values from any `ReadBytesExt` stream.
+pub trait ReadEndianExt: ReadBytesExt {
+	/// Reads a value whose bytes are in the order `O`, and converts it to host
+	/// endian.
+	fn read_endian<T, O>(&mut self) -> io::Result<T>
+	where T: Endian + AsBytes + FromBytes, O: EndianOrder {
+		let mut out = T::new_zeroed();
+		self.read_exact(out.as_bytes_mut())?;
+		Ok(O::from_order(out))
+	}
+}
+
+impl<R: ReadBytesExt + ?Sized> ReadEndianExt for R {}
+
+/// Writes `Endian` values to any `WriteBytesExt` stream.
+pub trait WriteEndianExt: WriteBytesExt {
+	/// Converts a value to the order `O`, and writes its bytes.
+	fn write_endian<T, O>(&mut self, value: T) -> io::Result<()>
+	where T: Endian + AsBytes, O: EndianOrder {
+		self.write_all(O::to_order(value).as_bytes())
+	}
+}
+
+impl<W: WriteBytesExt + ?Sized> WriteEndianExt for W {}
@@ -0,0 +1,278 @@
+/*! Zero-copy views between byte buffers and `Endian` types
+
+Once a value has been converted to a transport order, the only useful thing to
+do with it is to treat it as bytes. This module provides two marker traits that
+make that safe:
+
+- `AsBytes` types have no padding, so every byte of their representation is
+  initialized and can be viewed as a `&[u8]`.
+- `FromBytes` types accept every bit pattern, so any correctly sized and aligned
+  `&[u8]` can be viewed as one of them.
+
+The integer and float primitives are both. `bool` and `char` are only `AsBytes`,
+as most bit patterns are not valid values of those types. `#[derive(Endian)]`
+implements `AsBytes` on structs marked `#[endian(no_padding)]` and on enums, and
+implements `FromBytes` on structs marked `#[endian(from_bytes)]`.
+
+```rust
+use endian_trait::Endian;
+use endian_trait::bytes::{self, AsBytes, FromBytes};
+
+#[repr(C)]
+#[derive(Clone, Copy, Endian)]
+#[endian(from_bytes)]
+struct Header {
+    magic: u32,
+    length: u32,
+}
+
+let wire = Header { magic: 0xA1B2C3D4, length: 8 }.to_be();
+assert_eq!(wire.as_bytes()[.. 4], [0xA1, 0xB2, 0xC3, 0xD4]);
+
+let header: Header = bytes::read_be(wire.as_bytes()).unwrap();
+assert_eq!(header.length, 8);
+```
+!*/
+
+use super::{
+	Endian,
+	Endianness,
+	iter,
+};
+
+use core::{
+	fmt,
+	mem,
+	ptr,
+	slice,
+};
+
+/** Marks types whose every byte is initialized.
+
+A type is `AsBytes` when its representation contains no padding bytes, and all
+of its fields are themselves `AsBytes`. Such a value can be viewed as a byte
+slice without reading uninitialized memory.
+
+# Safety
+
+Implementors must guarantee that no byte of the type's representation is ever
+padding. Prefer `#[derive(Endian)]` with `#[endian(no_padding)]`, which checks
+this at compile time.
+**/
+pub unsafe trait AsBytes {
+	/// Views the value's representation as bytes.
+	fn as_bytes(&self) -> &[u8] {
+		unsafe {
+			slice::from_raw_parts(
+				self as *const Self as *const u8,
+				mem::size_of_val(self),
+			)
+		}
+	}
+
+	/// Views the value's representation as mutable bytes.
+	///
+	/// This requires `FromBytes` as well, since the caller may write any byte
+	/// values into the buffer.
+	fn as_bytes_mut(&mut self) -> &mut [u8]
+	where Self: FromBytes {
+		unsafe {
+			slice::from_raw_parts_mut(
+				self as *mut Self as *mut u8,
+				mem::size_of_val(self),
+			)
+		}
+	}
+}
+
+/** Marks types for which every bit pattern is a valid value.
+
+Such a type can be read out of any byte buffer of the correct width.
+
+# Safety
+
+Implementors must guarantee that every possible sequence of
+`size_of::<Self>()` bytes is a valid value of `Self`. Prefer
+`#[derive(Endian)]` with `#[endian(from_bytes)]`, which requires this of every
+field and checks at compile time that there is no padding.
+**/
+pub unsafe trait FromBytes: Sized {
+	/// Produces a value whose bytes are all zero.
+	fn new_zeroed() -> Self {
+		unsafe { mem::zeroed() }
+	}
+
+	/// Views a byte buffer as a reference to `Self`.
+	///
+	/// The buffer must be exactly as wide as `Self`, and aligned for it.
+	fn ref_from(bytes: &[u8]) -> Result<&Self, CastError> {
+		check::<Self>(bytes, 1)?;
+		Ok(unsafe { &*(bytes.as_ptr() as *const Self) })
+	}
+
+	/// Views a mutable byte buffer as a mutable reference to `Self`.
+	///
+	/// The buffer must be exactly as wide as `Self`, and aligned for it.
+	fn mut_from(bytes: &mut [u8]) -> Result<&mut Self, CastError>
+	where Self: AsBytes {
+		check::<Self>(bytes, 1)?;
+		Ok(unsafe { &mut *(bytes.as_mut_ptr() as *mut Self) })
+	}
+
+	/// Views a byte buffer as a slice of `Self`.
+	///
+	/// The buffer must be a whole multiple of the width of `Self`, and aligned
+	/// for it.
+	fn slice_from(bytes: &[u8]) -> Result<&[Self], CastError> {
+		let len = count::<Self>(bytes)?;
+		Ok(unsafe { slice::from_raw_parts(bytes.as_ptr() as *const Self, len) })
+	}
+
+	/// Views a mutable byte buffer as a mutable slice of `Self`.
+	///
+	/// The buffer must be a whole multiple of the width of `Self`, and aligned
+	/// for it.
+	fn slice_mut_from(bytes: &mut [u8]) -> Result<&mut [Self], CastError>
+	where Self: AsBytes {
+		let len = count::<Self>(bytes)?;
+		Ok(unsafe {
+			slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut Self, len)
+		})
+	}
+
+	/// Copies a value out of a byte buffer.
+	///
+	/// The buffer must be exactly as wide as `Self`, but need not be aligned.
+	fn read_from(bytes: &[u8]) -> Result<Self, CastError> {
+		if bytes.len() != mem::size_of::<Self>() {
+			return Err(CastError::Length);
+		}
+		Ok(unsafe { ptr::read_unaligned(bytes.as_ptr() as *const Self) })
+	}
+}
+
+/// Copies a big-endian value out of a byte buffer, and converts it to local
+/// order.
+///
+/// The buffer must be exactly as wide as `T`, but need not be aligned.
+pub fn read_be<T: Endian + FromBytes>(bytes: &[u8]) -> Result<T, CastError> {
+	T::read_from(bytes).map(Endian::from_be)
+}
+
+/// Copies a little-endian value out of a byte buffer, and converts it to local
+/// order.
+///
+/// The buffer must be exactly as wide as `T`, but need not be aligned.
+pub fn read_le<T: Endian + FromBytes>(bytes: &[u8]) -> Result<T, CastError> {
+	T::read_from(bytes).map(Endian::from_le)
+}
+
+/** Converts a byte buffer in place, as a sequence of `T` values.
+
+The buffer holds values of `T` in the given order, which are converted to host
+endian where they lie. For `FromBytes` types, converting from an order is the
+same rearrangement of bytes as converting to it, so this also prepares a buffer
+of host-endian values to be sent in that order.
+
+The buffer need not be aligned for `T`; a misaligned buffer is converted one
+copied value at a time, rather than by viewing it as a `&mut [T]`. It must be a
+whole multiple of the width of `T`. A buffer with a ragged tail is refused with
+`CastError::Length`, and left unchanged, rather than partly converted.
+
+On success, this returns the number of values converted.
+
+```rust
+use endian_trait::{Endianness, bytes::convert_bytes_as};
+
+let mut dma = [0u8; 9];
+dma[1 ..].copy_from_slice(&[0, 0, 0, 1, 0, 0, 0, 2]);
+assert_eq!(convert_bytes_as::<u32>(&mut dma[1 ..], Endianness::Big), Ok(2));
+assert_eq!(dma[1 .. 5], 1u32.to_ne_bytes());
+```
+**/
+pub fn convert_bytes_as<T>(
+	bytes: &mut [u8],
+	order: Endianness,
+) -> Result<usize, CastError>
+where T: Endian + AsBytes + FromBytes {
+	match T::slice_mut_from(bytes) {
+		Ok(elts) => {
+			let len = elts.len();
+			iter::convert_all(elts, |elt| order.convert_from(elt));
+			Ok(len)
+		},
+		Err(CastError::Alignment) => {
+			let width = mem::size_of::<T>();
+			for chunk in bytes.chunks_exact_mut(width) {
+				let elt = T::read_from(chunk)?;
+				chunk.copy_from_slice(order.convert_from(elt).as_bytes());
+			}
+			Ok(bytes.len() / width)
+		},
+		Err(err) => Err(err),
+	}
+}
+
+/// The reasons a byte buffer cannot be viewed as a typed value.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CastError {
+	/// The buffer is not the width that the requested type(s) need.
+	Length,
+	/// The buffer does not start at an address aligned for the requested type.
+	Alignment,
+}
+
+impl fmt::Display for CastError {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.write_str(match *self {
+			CastError::Length => "the buffer is the wrong length for this type",
+			CastError::Alignment => "the buffer is misaligned for this type",
+		})
+	}
+}
+
+/// Checks that a buffer holds exactly `n` aligned values of `T`.
+fn check<T>(bytes: &[u8], n: usize) -> Result<(), CastError> {
+	if bytes.len() != mem::size_of::<T>() * n {
+		return Err(CastError::Length);
+	}
+	if bytes.as_ptr() as usize % mem::align_of::<T>() != 0 {
+		return Err(CastError::Alignment);
+	}
+	Ok(())
+}
+
+/// Counts how many aligned values of `T` a buffer holds.
+fn count<T>(bytes: &[u8]) -> Result<usize, CastError> {
+	//  Any number of zero-sized values fit in an empty buffer, so there is no
+	//  answer to give.
+	let n = match mem::size_of::<T>() {
+		0 => return Err(CastError::Length),
+		size if bytes.len() % size != 0 => return Err(CastError::Length),
+		size => bytes.len() / size,
+	};
+	check::<T>(bytes, n)?;
+	Ok(n)
+}
+
+/// Marks the primitives that are both `AsBytes` and `FromBytes`.
+macro_rules! implbytes {
+	( $( $t:ty ),* ) => { $(
+		unsafe impl AsBytes for $t {}
+		unsafe impl FromBytes for $t {}
+	)* };
+}
+
+implbytes!(
+	i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize, f32, f64
+);
+
+//  `bool` and `char` have no padding, but only some bit patterns are valid.
+unsafe impl AsBytes for bool {}
+unsafe impl AsBytes for char {}
+
+//  Slices and arrays have no padding between their elements, as the width of a
+//  type is always a multiple of its alignment.
+unsafe impl<T: AsBytes> AsBytes for [T] {}
+unsafe impl<T: AsBytes, const N: usize> AsBytes for [T; N] {}
+unsafe impl<T: FromBytes, const N: usize> FromBytes for [T; N] {}
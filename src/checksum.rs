@@ -0,0 +1,63 @@
+/*! Checksums for derived wire layouts
+
+A struct field marked `#[endian(checksum = …)]` holds a checksum of the bytes
+that come before it in the struct's wire form. `#[derive(Codec)]` and
+`#[endian(wire)]` fill it in when writing, whatever the field held, and check it
+when reading. The algorithms named in the attribute are the functions in this
+module; `#[endian(checksum = custom(path))]` names any other function that takes
+`&[u8]` and returns the field's type.
+
+```rust
+use endian_trait::checksum;
+
+assert_eq!(checksum::crc32(b"123456789"), 0xCBF4_3926);
+assert_eq!(checksum::crc16_ccitt(b"123456789"), 0x29B1);
+assert_eq!(checksum::internet(&[0x45, 0x00, 0x00, 0x1C]), 0xBAE3);
+```
+!*/
+
+/// The CRC-32 of IEEE 802.3, as used by Ethernet, zlib, and PNG.
+///
+/// This is the reflected form of the polynomial `0x04C11DB7`, starting from
+/// and finishing with all bits set.
+pub fn crc32(bytes: &[u8]) -> u32 {
+	!bytes.iter().fold(!0, |crc, &byte| {
+		(0 .. 8).fold(crc ^ byte as u32, |crc, _| {
+			(crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg())
+		})
+	})
+}
+
+/// The CRC-16 with the CCITT polynomial `0x1021`, starting from `0xFFFF`.
+///
+/// This is the form that X.25 framing tools often call "CCITT-FALSE", without
+/// reflection or a final inversion.
+pub fn crc16_ccitt(bytes: &[u8]) -> u16 {
+	bytes.iter().fold(0xFFFF, |crc, &byte| {
+		(0 .. 8).fold(crc ^ (byte as u16) << 8, |crc, _| {
+			(crc << 1) ^ (0x1021 & (crc >> 15).wrapping_neg())
+		})
+	})
+}
+
+/// The Internet checksum of RFC 1071, as used by IPv4, ICMP, UDP, and TCP.
+///
+/// The bytes are summed as big-endian 16-bit words, with a zero byte after an
+/// odd final byte, and the result is the complement of their one's-complement
+/// sum. Store the field big-endian, as those protocols do.
+pub fn internet(bytes: &[u8]) -> u16 {
+	let mut sum = bytes.chunks(2)
+		.map(|pair| match *pair {
+			[hi, lo] => u16::from_be_bytes([hi, lo]) as u32,
+			[hi] => u16::from_be_bytes([hi, 0]) as u32,
+			_ => unreachable!("chunks are never empty"),
+		})
+		.fold(0u32, |sum, word| {
+			let (sum, carry) = sum.overflowing_add(word);
+			sum + carry as u32
+		});
+	while sum >> 16 != 0 {
+		sum = (sum & 0xFFFF) + (sum >> 16);
+	}
+	!(sum as u16)
+}
@@ -0,0 +1,449 @@
+/*! A compact binary codec built on `Endian`
+
+`Endian` puts each value's bytes in order, but leaves getting those bytes onto
+the wire to the user. The `Codec` trait finishes the job: it writes a value to
+any `io::Write`, and reads one back from any `io::Read`, in either byte order.
+
+The wire format is as plain as possible. Primitives are written as their bytes,
+`bool` as a single `0` or `1` byte, and `char` as its `u32` scalar value. Arrays
+write each element in turn. `#[derive(Codec)]` writes a struct's fields in
+declaration order, with no padding between them, and an enum as its `repr`
+integer, so one derive attribute is a complete wire format for a fixed-size
+record.
+
+```rust
+use endian_trait::{Codec, codec};
+
+#[derive(Codec, Debug, PartialEq)]
+struct Reading {
+    sensor: u8,
+    value: i32,
+    valid: bool,
+}
+
+let reading = Reading { sensor: 3, value: -2, valid: true };
+
+let mut wire = Vec::new();
+codec::encode_be(&reading, &mut wire).unwrap();
+assert_eq!(wire, [3, 0xFF, 0xFF, 0xFF, 0xFE, 1]);
+
+let back: Reading = codec::decode_be(&mut &wire[..]).unwrap();
+assert_eq!(back, reading);
+```
+
+Decoding fails with `io::ErrorKind::UnexpectedEof` when the input runs out, and
+with `io::ErrorKind::InvalidData` when it holds a value the type cannot take,
+such as a `bool` byte other than `0` or `1`.
+!*/
+
+use super::{
+	Endianness,
+	net,
+};
+
+use super::time::WireDuration;
+
+use super::bytes::{
+	AsBytes,
+	FromBytes,
+};
+
+use std::{
+	io::{
+		self,
+		Read,
+		Write,
+	},
+	iter::FromIterator,
+	mem::MaybeUninit,
+	net::{
+		Ipv4Addr,
+		Ipv6Addr,
+		SocketAddrV4,
+		SocketAddrV6,
+	},
+	vec::Vec,
+};
+
+/** Encode a value to, and decode it from, a byte stream in a given order.
+
+This is implemented on the primitives, on arrays of `Codec` types, on the
+`std::net` address types, on `WireDuration`, and by `#[derive(Codec)]` on
+structs whose fields are all `Codec`, and on enums with an integer `repr`.
+**/
+pub trait Codec: Sized {
+	/// Writes the value to a stream, with its bytes in the given order.
+	fn encode<W>(&self, order: Endianness, writer: &mut W) -> io::Result<()>
+	where W: Write + ?Sized;
+
+	/// Reads a value from a stream whose bytes are in the given order.
+	fn decode<R>(order: Endianness, reader: &mut R) -> io::Result<Self>
+	where R: Read + ?Sized;
+}
+
+/** Decode a value whose magic number tells which byte order it is in.
+
+`#[derive(Codec)]` implements this on structs whose first field is marked
+`#[endian(magic = N, detect_order)]`. The magic number is read in both orders,
+and whichever one matches is used for the rest of the struct. A magic number
+that reads the same both ways, such as a single byte, cannot tell them apart,
+and is taken to be big-endian.
+**/
+pub trait DetectOrder: Codec {
+	/// Reads a value, and the byte order that its magic number showed it to be
+	/// in.
+	fn decode_detect<R>(reader: &mut R) -> io::Result<(Self, Endianness)>
+	where R: Read + ?Sized;
+}
+
+/// Writes a value to a stream in big-endian order.
+pub fn encode_be<T: Codec>(value: &T, writer: &mut impl Write) -> io::Result<()> {
+	value.encode(Endianness::Big, writer)
+}
+
+/// Writes a value to a stream in little-endian order.
+pub fn encode_le<T: Codec>(value: &T, writer: &mut impl Write) -> io::Result<()> {
+	value.encode(Endianness::Little, writer)
+}
+
+/// Reads a big-endian value from a stream.
+pub fn decode_be<T: Codec>(reader: &mut impl Read) -> io::Result<T> {
+	T::decode(Endianness::Big, reader)
+}
+
+/// Reads a little-endian value from a stream.
+pub fn decode_le<T: Codec>(reader: &mut impl Read) -> io::Result<T> {
+	T::decode(Endianness::Little, reader)
+}
+
+/// Builds the error for bytes that do not form a valid value.
+pub fn invalid_data(msg: &str) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Builds the error for a value that cannot be encoded.
+pub fn invalid_input(msg: &str) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+/// Writes `len` zero bytes, for padding and reserved space.
+pub fn pad<W>(len: usize, writer: &mut W) -> io::Result<()>
+where W: Write + ?Sized {
+	io::copy(&mut io::repeat(0).take(len as u64), writer).map(drop)
+}
+
+/// Reads and discards `len` bytes, and reports whether they were all zero.
+pub fn skip<R>(len: usize, reader: &mut R) -> io::Result<bool>
+where R: Read + ?Sized {
+	let mut zero = true;
+	let mut left = len;
+	let mut chunk = [0u8; 64];
+	while left > 0 {
+		let part = &mut chunk[.. left.min(64)];
+		reader.read_exact(part)?;
+		zero &= part.iter().all(|&b| b == 0);
+		left -= part.len();
+	}
+	Ok(zero)
+}
+
+/// Writes each value in a sequence, without its length.
+///
+/// This writes the fields marked `#[endian(len = "…")]`, whose length is
+/// written by another field.
+pub fn encode_seq<T, W>(
+	items: &[T],
+	order: Endianness,
+	writer: &mut W,
+) -> io::Result<()>
+where T: Codec, W: Write + ?Sized {
+	items.iter().try_for_each(|item| item.encode(order, writer))
+}
+
+/// Reads `len` values into a collection, such as a `Vec<T>` or `Box<[T]>`.
+///
+/// The collection grows as values arrive, so a corrupt `len` runs out of input
+/// rather than memory.
+pub fn decode_seq<C, T, R>(
+	len: usize,
+	order: Endianness,
+	reader: &mut R,
+) -> io::Result<C>
+where C: FromIterator<T>, T: Codec, R: Read + ?Sized {
+	(0 .. len).map(|_| T::decode(order, reader)).collect()
+}
+
+/** A stream that counts the bytes moved through it.
+
+`#[derive(Codec)]` wraps its stream in this to find where `#[endian(align)]`
+fields start, as the codec has no other record of its position.
+**/
+#[derive(Clone, Debug, Default)]
+pub struct Counted<T> {
+	inner: T,
+	count: usize,
+}
+
+impl<T> Counted<T> {
+	/// Wraps a stream, with the count starting at zero.
+	pub fn new(inner: T) -> Self {
+		Self { inner, count: 0 }
+	}
+
+	/// The number of bytes written to or read from the stream so far.
+	pub fn count(&self) -> usize {
+		self.count
+	}
+
+	/// Unwraps the stream.
+	pub fn into_inner(self) -> T {
+		self.inner
+	}
+}
+
+/** A stream that keeps a copy of the bytes moved through it.
+
+`#[derive(Codec)]` wraps its stream in this to compute `#[endian(checksum)]`
+fields over the bytes that precede them.
+**/
+#[derive(Clone, Debug, Default)]
+pub struct Recorded<T> {
+	inner: T,
+	bytes: Vec<u8>,
+}
+
+impl<T> Recorded<T> {
+	/// Wraps a stream, with nothing recorded.
+	pub fn new(inner: T) -> Self {
+		Self { inner, bytes: Vec::new() }
+	}
+
+	/// The bytes written to or read from the stream so far.
+	pub fn bytes(&self) -> &[u8] {
+		&self.bytes
+	}
+
+	/// The number of bytes written to or read from the stream so far.
+	pub fn count(&self) -> usize {
+		self.bytes.len()
+	}
+
+	/// Unwraps the stream.
+	pub fn into_inner(self) -> T {
+		self.inner
+	}
+}
+
+impl<T: Write> Write for Recorded<T> {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		let len = self.inner.write(buf)?;
+		self.bytes.extend_from_slice(&buf[.. len]);
+		Ok(len)
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		self.inner.flush()
+	}
+}
+
+impl<T: Read> Read for Recorded<T> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		let len = self.inner.read(buf)?;
+		self.bytes.extend_from_slice(&buf[.. len]);
+		Ok(len)
+	}
+}
+
+impl<T: Write> Write for Counted<T> {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		let len = self.inner.write(buf)?;
+		self.count += len;
+		Ok(len)
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		self.inner.flush()
+	}
+}
+
+impl<T: Read> Read for Counted<T> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		let len = self.inner.read(buf)?;
+		self.count += len;
+		Ok(len)
+	}
+}
+
+/// Implement Codec on types whose wire form is their converted bytes.
+macro_rules! implcodec {
+	( $( $t:ty ),* ) => { $(
+		impl Codec for $t {
+			fn encode<W>(&self, order: Endianness, writer: &mut W) -> io::Result<()>
+			where W: Write + ?Sized {
+				writer.write_all(order.convert_to(*self).as_bytes())
+			}
+
+			fn decode<R>(order: Endianness, reader: &mut R) -> io::Result<Self>
+			where R: Read + ?Sized {
+				let mut out = Self::new_zeroed();
+				reader.read_exact(out.as_bytes_mut())?;
+				Ok(order.convert_from(out))
+			}
+		}
+	)* };
+}
+
+implcodec!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, f32, f64);
+
+/// `bool` is a single byte, which must be `0` or `1`.
+impl Codec for bool {
+	fn encode<W>(&self, order: Endianness, writer: &mut W) -> io::Result<()>
+	where W: Write + ?Sized {
+		(*self as u8).encode(order, writer)
+	}
+
+	fn decode<R>(order: Endianness, reader: &mut R) -> io::Result<Self>
+	where R: Read + ?Sized {
+		match u8::decode(order, reader)? {
+			0 => Ok(false),
+			1 => Ok(true),
+			_ => Err(invalid_data("a `bool` must be encoded as 0 or 1")),
+		}
+	}
+}
+
+/// `char` is its scalar value, as a `u32`.
+///
+/// Unlike `Endian::from_be`, decoding an invalid scalar value is an error
+/// rather than a panic, as the bytes come from outside the program.
+impl Codec for char {
+	fn encode<W>(&self, order: Endianness, writer: &mut W) -> io::Result<()>
+	where W: Write + ?Sized {
+		(*self as u32).encode(order, writer)
+	}
+
+	fn decode<R>(order: Endianness, reader: &mut R) -> io::Result<Self>
+	where R: Read + ?Sized {
+		std::char::from_u32(u32::decode(order, reader)?)
+			.ok_or_else(|| invalid_data("not a Unicode scalar value"))
+	}
+}
+
+/// IPv4 addresses are their 4 octets.
+impl Codec for Ipv4Addr {
+	fn encode<W>(&self, _: Endianness, writer: &mut W) -> io::Result<()>
+	where W: Write + ?Sized {
+		writer.write_all(&self.octets())
+	}
+
+	fn decode<R>(order: Endianness, reader: &mut R) -> io::Result<Self>
+	where R: Read + ?Sized {
+		<[u8; 4]>::decode(order, reader).map(Self::from)
+	}
+}
+
+/// IPv6 addresses are their 16 octets.
+impl Codec for Ipv6Addr {
+	fn encode<W>(&self, _: Endianness, writer: &mut W) -> io::Result<()>
+	where W: Write + ?Sized {
+		writer.write_all(&self.octets())
+	}
+
+	fn decode<R>(order: Endianness, reader: &mut R) -> io::Result<Self>
+	where R: Read + ?Sized {
+		<[u8; 16]>::decode(order, reader).map(Self::from)
+	}
+}
+
+/// IPv4 socket addresses use the layout of `net::v4_to_bytes`.
+impl Codec for SocketAddrV4 {
+	fn encode<W>(&self, order: Endianness, writer: &mut W) -> io::Result<()>
+	where W: Write + ?Sized {
+		writer.write_all(&net::v4_to_bytes(*self, order))
+	}
+
+	fn decode<R>(order: Endianness, reader: &mut R) -> io::Result<Self>
+	where R: Read + ?Sized {
+		let mut bytes = [0; 6];
+		reader.read_exact(&mut bytes)?;
+		Ok(net::v4_from_bytes(bytes, order))
+	}
+}
+
+/// IPv6 socket addresses use the layout of `net::v6_to_bytes`.
+impl Codec for SocketAddrV6 {
+	fn encode<W>(&self, order: Endianness, writer: &mut W) -> io::Result<()>
+	where W: Write + ?Sized {
+		writer.write_all(&net::v6_to_bytes(*self, order))
+	}
+
+	fn decode<R>(order: Endianness, reader: &mut R) -> io::Result<Self>
+	where R: Read + ?Sized {
+		let mut bytes = [0; 26];
+		reader.read_exact(&mut bytes)?;
+		Ok(net::v6_from_bytes(bytes, order))
+	}
+}
+
+/// Durations are their seconds, then their nanoseconds, with no padding.
+///
+/// Decoding does not check the nanoseconds; converting to `Duration` does.
+impl Codec for WireDuration {
+	fn encode<W>(&self, order: Endianness, writer: &mut W) -> io::Result<()>
+	where W: Write + ?Sized {
+		self.secs.encode(order, writer)?;
+		self.nanos.encode(order, writer)
+	}
+
+	fn decode<R>(order: Endianness, reader: &mut R) -> io::Result<Self>
+	where R: Read + ?Sized {
+		Ok(WireDuration {
+			secs: u64::decode(order, reader)?,
+			nanos: u32::decode(order, reader)?,
+		})
+	}
+}
+
+/// Arrays are each of their elements in turn.
+impl<T: Codec, const N: usize> Codec for [T; N] {
+	fn encode<W>(&self, order: Endianness, writer: &mut W) -> io::Result<()>
+	where W: Write + ?Sized {
+		self.iter().try_for_each(|elt| elt.encode(order, writer))
+	}
+
+	fn decode<R>(order: Endianness, reader: &mut R) -> io::Result<Self>
+	where R: Read + ?Sized {
+		let mut out = Partial::<T, N> {
+			elts: [const { MaybeUninit::uninit() }; N],
+			len: 0,
+		};
+		while out.len < N {
+			out.elts[out.len].write(T::decode(order, reader)?);
+			out.len += 1;
+		}
+		//  Take the elements out, and leave nothing for the guard to drop.
+		out.len = 0;
+		//  SAFETY: the loop initialized all `N` elements, and `MaybeUninit<T>`
+		//  has the same layout as `T`.
+		Ok(unsafe { (out.elts.as_ptr() as *const [T; N]).read() })
+	}
+}
+
+/// An array that is being decoded, of which the first `len` elements are
+/// initialized.
+///
+/// Dropping it drops those elements, so that an array which fails partway
+/// through decoding does not leak the elements before the failure.
+struct Partial<T, const N: usize> {
+	elts: [MaybeUninit<T>; N],
+	len: usize,
+}
+
+impl<T, const N: usize> Drop for Partial<T, N> {
+	fn drop(&mut self) {
+		for elt in &mut self.elts[.. self.len] {
+			//  SAFETY: the first `len` elements are initialized.
+			unsafe { elt.assume_init_drop() }
+		}
+	}
+}
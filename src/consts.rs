@@ -0,0 +1,159 @@
+/*! Byte order conversions in `const` contexts
+
+Trait methods cannot be called in `const` items, so `Endian` cannot build a wire
+constant, such as a magic header or a default packet, at compile time. The
+`const_be` and `const_le` functions can, for any type that implements
+`ConstEndian`:
+
+```rust
+use endian_trait::{Endian, const_be};
+
+const MAGIC: u32 = const_be::<u32>(0xCAFE_BABE);
+
+#[derive(Clone, Copy, Endian)]
+#[endian(const_fn)]
+struct Header {
+    magic: u32,
+    len: u16,
+}
+
+const EMPTY: Header = Header { magic: 0xCAFE_BABE, len: 0 }.to_be_const();
+assert_eq!(EMPTY.magic, MAGIC);
+```
+
+`ConstEndian` does not run the `Endian` methods. It lists the byte ranges that
+they reverse, and the functions here reverse those ranges directly. It is
+implemented on the integers, the floats, and `bool`, and
+`#[derive(Endian)]` implements it on structs marked `#[endian(const_fn)]`, whose
+fields must implement it as well. Such structs also gain `to_be_const`,
+`to_le_const`, `from_be_const`, and `from_le_const` methods.
+
+`char` does not implement it, as reversing a `char`'s bytes rarely produces a
+valid `char`, and `const` evaluation rejects invalid values outright.
+!*/
+
+use super::{
+	Endian,
+	Endianness,
+};
+
+use core::mem;
+
+/** A type whose byte order conversion is a fixed set of byte reversals.
+
+Each conversion of the type reverses the bytes in each of `SWAPS`, and leaves
+the rest of the value alone. This makes it possible to convert the type without
+calling any trait methods, and so in `const` contexts.
+
+# Safety
+
+Every range must lie within the type's bytes, no two ranges may overlap, and
+reversing any of them must leave a valid value of the type. The `Endian` impl
+must produce the same bytes as reversing the ranges does.
+**/
+pub unsafe trait ConstEndian: Endian {
+	/// The byte ranges that a conversion reverses, as offsets from the start
+	/// of the value and widths.
+	const SWAPS: &'static [(usize, usize)];
+}
+
+/// Converts a value between host endian and big-endian order in a `const`
+/// context.
+///
+/// Conversions in both directions are the same, so this stands for both
+/// `Endian::to_be` and `Endian::from_be`.
+pub const fn const_be<T: ConstEndian>(value: T) -> T {
+	match Endianness::NATIVE {
+		Endianness::Big => value,
+		Endianness::Little => swap(value),
+	}
+}
+
+/// Converts a value between host endian and little-endian order in a `const`
+/// context.
+///
+/// Conversions in both directions are the same, so this stands for both
+/// `Endian::to_le` and `Endian::from_le`.
+pub const fn const_le<T: ConstEndian>(value: T) -> T {
+	match Endianness::NATIVE {
+		Endianness::Big => swap(value),
+		Endianness::Little => value,
+	}
+}
+
+/// Reverses each of a value's `SWAPS`.
+const fn swap<T: ConstEndian>(mut value: T) -> T {
+	let base = &mut value as *mut T as *mut u8;
+	let mut idx = 0;
+	while idx < T::SWAPS.len() {
+		let (offset, width) = T::SWAPS[idx];
+		let mut lo = 0;
+		while lo < width / 2 {
+			let hi = width - 1 - lo;
+			//  SAFETY: the trait promises that each range is in the value, and
+			//  that reversing it leaves a valid value.
+			unsafe {
+				let a = base.add(offset + lo);
+				let b = base.add(offset + hi);
+				let tmp = a.read();
+				a.write(b.read());
+				b.write(tmp);
+			}
+			lo += 1;
+		}
+		idx += 1;
+	}
+	value
+}
+
+/// Counts the ranges in the `SWAPS` of a struct's fields.
+///
+/// This is used by `#[derive(Endian)]`, and is not part of the public API.
+#[doc(hidden)]
+pub const fn count(fields: &[&[(usize, usize)]]) -> usize {
+	let mut total = 0;
+	let mut idx = 0;
+	while idx < fields.len() {
+		total += fields[idx].len();
+		idx += 1;
+	}
+	total
+}
+
+/// Collects the `SWAPS` of a struct's fields, each moved to the field's offset
+/// in the struct, into the struct's own `SWAPS`.
+///
+/// This is used by `#[derive(Endian)]`, and is not part of the public API.
+#[doc(hidden)]
+pub const fn merge<const N: usize>(
+	fields: &[(usize, &[(usize, usize)])],
+) -> [(usize, usize); N] {
+	let mut out = [(0, 0); N];
+	let mut len = 0;
+	let mut idx = 0;
+	while idx < fields.len() {
+		let (base, swaps) = fields[idx];
+		let mut each = 0;
+		while each < swaps.len() {
+			let (offset, width) = swaps[each];
+			out[len] = (base + offset, width);
+			len += 1;
+			each += 1;
+		}
+		idx += 1;
+	}
+	assert!(len == N, "`N` must be the number of ranges in `fields`");
+	out
+}
+
+/// Implement ConstEndian on the primitives, as one range over the whole value.
+macro_rules! implconst {
+	( $( $t:ty ),* ) => { $(
+		unsafe impl ConstEndian for $t {
+			const SWAPS: &'static [(usize, usize)] =
+				&[(0, mem::size_of::<$t>())];
+		}
+	)* };
+}
+
+implconst!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, f32, f64, bool);
@@ -0,0 +1,242 @@
+/*! Annotated hex dumps of converted values
+
+When a protocol goes wrong, the question is usually which bytes a value turned
+into. `EndianDump` answers it: `dump_be` and `dump_le` format the value's
+representation after `Endian::to_be` or `Endian::to_le` as a hex dump, with one
+line for each primitive in it, labelled with its offset, the path of fields that
+leads to it, and its type.
+
+`#[derive(EndianDump)]` implements the trait on structs whose fields implement
+it, taking the field names and offsets from the struct itself, and on enums
+with an integer `repr`.
+
+```rust
+use endian_trait::{Endian, EndianDump};
+
+#[repr(C)]
+#[derive(Clone, Copy, Endian, EndianDump)]
+struct Header {
+    kind: u8,
+    len: u16,
+    id: u32,
+}
+
+let header = Header { kind: 1, len: 0x0203, id: 0x04050607 };
+println!("{:?}", header.dump_be());
+```
+
+prints
+
+```text
+<type name> (Big endian, 8 bytes)
+0000  01                                               kind: u8
+0002  02 03                                            len: u16
+0004  04 05 06 07                                      id: u32
+```
+
+where `<type name>` is the `core::any::type_name` of `Header`, such as
+`my_crate::Header`. The compiler chooses how to spell it, so it may differ
+between crates and compiler versions.
+
+Lines follow the fields in declaration order, and their offsets are where the
+fields actually are, so a struct that Rust has reordered shows offsets out of
+order. Padding between fields has no line of its own, but shows as a gap in the
+offsets.
+!*/
+
+use super::{
+	Endian,
+	Endianness,
+};
+
+use super::bytes::AsBytes;
+
+use core::{
+	any,
+	fmt,
+	mem,
+};
+
+/** Describe a value's converted representation, part by part.
+
+Implementations report each of their parts to a `Dumper`: fields and elements
+through `field` and `element`, and the primitives at the bottom through
+`scalar` or `bytes`.
+**/
+pub trait EndianDump {
+	/// Reports the parts of the value's representation to the dumper, in the
+	/// dumper's byte order.
+	fn dump(&self, dumper: &mut Dumper<'_, '_>) -> fmt::Result;
+
+	/// Formats the value's big-endian representation as a hex dump.
+	fn dump_be(&self) -> Dump<'_, Self> {
+		Dump { value: self, order: Endianness::Big }
+	}
+
+	/// Formats the value's little-endian representation as a hex dump.
+	fn dump_le(&self) -> Dump<'_, Self> {
+		Dump { value: self, order: Endianness::Little }
+	}
+}
+
+/// A value's representation in one byte order, which formats as a hex dump.
+pub struct Dump<'a, T: ?Sized> {
+	value: &'a T,
+	order: Endianness,
+}
+
+impl<T: EndianDump + ?Sized> fmt::Debug for Dump<'_, T> {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(
+			fmt,
+			"{} ({:?} endian, {} bytes)",
+			any::type_name::<T>(),
+			self.order,
+			mem::size_of_val(self.value),
+		)?;
+		self.value.dump(&mut Dumper {
+			fmt,
+			order: self.order,
+			offset: 0,
+			path: None,
+		})
+	}
+}
+
+impl<T: EndianDump + ?Sized> fmt::Display for Dump<'_, T> {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Debug::fmt(self, fmt)
+	}
+}
+
+/// Writes the lines of a hex dump, keeping track of where in the value it is.
+pub struct Dumper<'a, 'b> {
+	fmt: &'a mut fmt::Formatter<'b>,
+	order: Endianness,
+	offset: usize,
+	path: Option<&'a Path<'a>>,
+}
+
+impl Dumper<'_, '_> {
+	/// The byte order being dumped.
+	pub fn order(&self) -> Endianness {
+		self.order
+	}
+
+	/// Dumps a named field, which starts `offset` bytes into the current value.
+	pub fn field<T>(
+		&mut self,
+		name: &'static str,
+		offset: usize,
+		value: &T,
+	) -> fmt::Result
+	where T: EndianDump + ?Sized {
+		self.nest(Step::Field(name), offset, value)
+	}
+
+	/// Dumps an element of a sequence, which starts `offset` bytes into the
+	/// current value.
+	pub fn element<T>(
+		&mut self,
+		index: usize,
+		offset: usize,
+		value: &T,
+	) -> fmt::Result
+	where T: EndianDump + ?Sized {
+		self.nest(Step::Index(index), offset, value)
+	}
+
+	/// Converts a primitive to the dump's byte order, and writes its line.
+	pub fn scalar<T>(&mut self, ty: &str, value: T) -> fmt::Result
+	where T: AsBytes + Endian {
+		let value = self.order.convert_to(value);
+		self.bytes(ty, value.as_bytes())
+	}
+
+	/// Writes the line for bytes that are already in the dump's byte order.
+	pub fn bytes(&mut self, ty: &str, bytes: &[u8]) -> fmt::Result {
+		write!(self.fmt, "{:04X} ", self.offset)?;
+		for byte in bytes {
+			write!(self.fmt, " {:02X}", byte)?;
+		}
+		//  Lines line up for values as wide as a `u128`.
+		for _ in bytes.len() .. 16 {
+			self.fmt.write_str("   ")?;
+		}
+		match self.path {
+			Some(path) => writeln!(self.fmt, "  {}: {}", path, ty),
+			None => writeln!(self.fmt, "  {}", ty),
+		}
+	}
+
+	/// Dumps a part of the current value, with its step added to the path.
+	fn nest<T>(&mut self, step: Step, offset: usize, value: &T) -> fmt::Result
+	where T: EndianDump + ?Sized {
+		let path = Path { step, parent: self.path };
+		value.dump(&mut Dumper {
+			fmt: &mut *self.fmt,
+			order: self.order,
+			offset: self.offset + offset,
+			path: Some(&path),
+		})
+	}
+}
+
+/// The fields and indices that lead from the dumped value to one of its parts.
+///
+/// Each level of nesting keeps its step on the stack, and points to the level
+/// above it.
+struct Path<'a> {
+	step: Step,
+	parent: Option<&'a Path<'a>>,
+}
+
+/// One step into a value.
+#[derive(Clone, Copy)]
+enum Step {
+	Field(&'static str),
+	Index(usize),
+}
+
+impl fmt::Display for Path<'_> {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		if let Some(parent) = self.parent {
+			fmt::Display::fmt(parent, fmt)?;
+		}
+		match (self.step, self.parent) {
+			(Step::Field(name), None) => fmt.write_str(name),
+			(Step::Field(name), Some(_)) => write!(fmt, ".{}", name),
+			(Step::Index(idx), _) => write!(fmt, "[{}]", idx),
+		}
+	}
+}
+
+/// Implement EndianDump on the primitives, as single lines.
+macro_rules! impldump {
+	( $( $t:ty ),* ) => { $(
+		impl EndianDump for $t {
+			fn dump(&self, dumper: &mut Dumper<'_, '_>) -> fmt::Result {
+				dumper.scalar(stringify!($t), *self)
+			}
+		}
+	)* };
+}
+
+impldump!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, f32, f64, bool);
+
+/// `char` is dumped as its scalar value, as converting a `char` itself can
+/// produce one that is not valid.
+impl EndianDump for char {
+	fn dump(&self, dumper: &mut Dumper<'_, '_>) -> fmt::Result {
+		dumper.scalar("char", *self as u32)
+	}
+}
+
+/// Arrays are each of their elements in turn.
+impl<T: EndianDump, const N: usize> EndianDump for [T; N] {
+	fn dump(&self, dumper: &mut Dumper<'_, '_>) -> fmt::Result {
+		self.iter().enumerate().try_for_each(|(idx, elt)| {
+			dumper.element(idx, idx * mem::size_of::<T>(), elt)
+		})
+	}
+}
@@ -0,0 +1,174 @@
+/*! Conversions over iterators
+
+`EndianIterExt` adds adapters to every iterator of `Endian` values, so that
+records can be converted as they stream past:
+
+```rust
+use endian_trait::{EndianIterExt, Endianness};
+
+let wire = [0x0100u16.to_be(), 0x0200u16.to_be()];
+let local = wire.iter().copied().from_be().collect::<Vec<_>>();
+assert_eq!(local, [0x0100, 0x0200]);
+
+let order = Endianness::Little;
+let out = local.into_iter().to_order(order).collect::<Vec<_>>();
+assert_eq!(out, [0x0100u16.to_le(), 0x0200u16.to_le()]);
+```
+
+The adapters keep the length and direction of the source iterator, so they can
+still be reversed or counted exactly. To convert values where they are stored,
+pass an `iter_mut()` to `convert_all`.
+!*/
+
+use super::{
+	Endian,
+	Endianness,
+};
+
+use core::{
+	fmt,
+	iter::FusedIterator,
+	mem,
+	ptr,
+};
+
+/// Adapters that convert each item of an iterator.
+#[allow(clippy::wrong_self_convention)]
+pub trait EndianIterExt: Iterator + Sized
+where Self::Item: Endian {
+	/// Converts each item from host endian to big-endian order.
+	fn to_be(self) -> Convert<Self> {
+		Convert::new(self, Endian::to_be)
+	}
+
+	/// Converts each item from host endian to little-endian order.
+	fn to_le(self) -> Convert<Self> {
+		Convert::new(self, Endian::to_le)
+	}
+
+	/// Converts each item from big-endian order to host endian.
+	fn from_be(self) -> Convert<Self> {
+		Convert::new(self, Endian::from_be)
+	}
+
+	/// Converts each item from little-endian order to host endian.
+	fn from_le(self) -> Convert<Self> {
+		Convert::new(self, Endian::from_le)
+	}
+
+	/// Converts each item from host endian to the given order.
+	fn to_order(self, order: Endianness) -> Convert<Self> {
+		match order {
+			Endianness::Big => self.to_be(),
+			Endianness::Little => self.to_le(),
+		}
+	}
+
+	/// Converts each item from the given order to host endian.
+	fn from_order(self, order: Endianness) -> Convert<Self> {
+		match order {
+			Endianness::Big => self.from_be(),
+			Endianness::Little => self.from_le(),
+		}
+	}
+}
+
+impl<I> EndianIterExt for I
+where I: Iterator, I::Item: Endian {}
+
+/** An iterator that converts each item of another.
+
+This is built by the methods on `EndianIterExt`.
+**/
+pub struct Convert<I: Iterator> {
+	iter: I,
+	func: fn(I::Item) -> I::Item,
+}
+
+impl<I: Iterator> Convert<I> {
+	fn new(iter: I, func: fn(I::Item) -> I::Item) -> Self {
+		Convert { iter, func }
+	}
+
+	/// Returns the iterator being converted.
+	pub fn into_inner(self) -> I {
+		self.iter
+	}
+}
+
+impl<I: Iterator + Clone> Clone for Convert<I> {
+	fn clone(&self) -> Self {
+		Convert::new(self.iter.clone(), self.func)
+	}
+}
+
+impl<I: Iterator + fmt::Debug> fmt::Debug for Convert<I> {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("Convert")
+			.field("iter", &self.iter)
+			.finish()
+	}
+}
+
+impl<I: Iterator> Iterator for Convert<I> {
+	type Item = I::Item;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().map(self.func)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<I: DoubleEndedIterator> DoubleEndedIterator for Convert<I> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.iter.next_back().map(self.func)
+	}
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for Convert<I> {
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<I: FusedIterator> FusedIterator for Convert<I> {}
+
+/** Converts each value that an iterator points to, in place.
+
+```rust
+use endian_trait::{Endian, iter::convert_all};
+
+let mut words = [0x0102u16, 0x0304];
+convert_all(words.iter_mut(), Endian::to_be);
+assert_eq!(words[1].to_ne_bytes(), [3, 4]);
+```
+
+The process aborts if `func` panics, as the value it was converting has already
+been moved out of its place.
+**/
+pub fn convert_all<'a, T, F>(
+	iter: impl IntoIterator<Item = &'a mut T>,
+	mut func: F,
+)
+where T: Endian + 'a, F: FnMut(T) -> T {
+	for elt in iter {
+		let bomb = Bomb;
+		unsafe {
+			let ptr = elt as *mut T;
+			ptr::write(ptr, func(ptr::read(ptr)));
+		}
+		mem::forget(bomb);
+	}
+}
+
+/// Panics when dropped, which aborts if it is dropped while unwinding.
+struct Bomb;
+
+impl Drop for Bomb {
+	fn drop(&mut self) {
+		panic!("an `Endian` conversion panicked while converting in place");
+	}
+}
@@ -10,6 +10,12 @@ to `i32::from_be(n: i32)`
 #![deny(missing_docs)]
 #![no_std]
 
+#[cfg(feature = "bytemuck")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
 //  Reëxport the custom-derive so that users don't need two crates explicitly.
 #[allow(unused_imports)]
 pub use endian_trait_derive::*;
@@ -34,14 +40,58 @@ pub trait Endian {
 	/// Converts from big-endian order to host endian.
 	///
 	/// On big-endian platforms, this is a no-op and should be compiled out.
+	//  Named after the inherent methods on the integers, which take `self`.
+	#[allow(clippy::wrong_self_convention)]
 	fn from_be(self) -> Self;
 
 	/// Converts from little-endian order to host endian.
 	///
 	/// On little-endian platforms, this is a no-op and should be compiled out.
+	//  Named after the inherent methods on the integers, which take `self`.
+	#[allow(clippy::wrong_self_convention)]
 	fn from_le(self) -> Self;
 }
 
+/** A byte order, for when the conversion to perform is chosen at runtime.
+
+The `Endian` methods name their order statically. Code that is handed an order
+as data, such as a codec reading a format that records its own endianness, can
+use this to dispatch to the matching method.
+**/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Endianness {
+	/// Most significant byte first; network order.
+	Big,
+	/// Least significant byte first.
+	Little,
+}
+
+impl Endianness {
+	/// The byte order of the target platform.
+	#[cfg(target_endian = "big")]
+	pub const NATIVE: Self = Endianness::Big;
+
+	/// The byte order of the target platform.
+	#[cfg(target_endian = "little")]
+	pub const NATIVE: Self = Endianness::Little;
+
+	/// Converts a value from host endian to this order.
+	pub fn convert_to<T: Endian>(self, value: T) -> T {
+		match self {
+			Endianness::Big => value.to_be(),
+			Endianness::Little => value.to_le(),
+		}
+	}
+
+	/// Converts a value from this order to host endian.
+	pub fn convert_from<T: Endian>(self, value: T) -> T {
+		match self {
+			Endianness::Big => value.from_be(),
+			Endianness::Little => value.from_le(),
+		}
+	}
+}
+
 /** Implementing Endian on the integer primitives just means delegating to their
 inherent methods. As there are many integer primitives, this macro prevents
 needless code duplication.
@@ -110,6 +160,7 @@ The `to_` functions will emit potentially invalid `char` values, and this is to
 be expected. The `from_` functions, however, will panic if they are about to
 emit an invalid `char` pattern.
 **/
+#[allow(unknown_lints, unnecessary_transmutes)]
 impl Endian for char {
 	/// Attempts to create a local `char` from a big-endian value.
 	///
@@ -155,12 +206,74 @@ impl Endian for char {
 }
 
 //  Implement on the integer primitives
-implendian!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128);
+implendian!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize);
 
 //  Implement on floats
 implendian_f!(f32, f64);
 
+pub mod bytes;
+
 #[cfg(feature = "arrays")]
 mod arrays;
 
+pub mod atomics;
+
+pub use atomics::AtomicEndian;
+
+#[cfg(feature = "bytemuck")]
+pub mod bytemuck;
+
+#[cfg(feature = "byteorder")]
+pub mod byteorder;
+
+pub mod checksum;
+
+#[cfg(feature = "std")]
+pub mod codec;
+
+#[cfg(feature = "std")]
+pub use codec::Codec;
+
+pub mod consts;
+
+pub use consts::{
+	ConstEndian,
+	const_be,
+	const_le,
+};
+
+pub mod dump;
+
+pub use dump::EndianDump;
+
+pub mod iter;
+
+pub use iter::EndianIterExt;
+
+pub mod net;
+
+#[cfg(feature = "nom")]
+pub mod nom;
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub mod time;
+
+pub mod unaligned;
+
+pub use unaligned::Unaligned;
+
+mod wrappers;
+
+pub mod wire;
+
+pub use wire::Wire;
+
+#[cfg(feature = "zerocopy")]
+pub mod zerocopy;
+
 mod slices;
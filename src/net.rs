@@ -0,0 +1,178 @@
+/*! Implementations on the `core::net` address types
+
+IP addresses are stored as their octets, in network order, on every platform, so
+the `Endian` conversions leave them untouched. Socket addresses also carry
+integers (a port, and for IPv6 the flow information and scope ID), which are
+stored in host order and are converted like any other integer. A struct that
+holds these types can therefore derive `Endian`, and its converted form carries
+the address exactly as it appears in a network header.
+
+The socket address types do not expose their memory layout, so they cannot be
+viewed as bytes. The functions in this module instead describe a fixed wire
+layout for each, which the `Codec` implementations also use:
+
+- `SocketAddrV4`: 6 bytes; the 4 address octets, then the port.
+- `SocketAddrV6`: 26 bytes; the 16 address octets, then the port, the flow
+  information, and the scope ID.
+
+The address octets are never reordered. The integers are written in the order
+given to each function.
+!*/
+
+use super::{
+	Endian,
+	Endianness,
+};
+
+use core::net::{
+	IpAddr,
+	Ipv4Addr,
+	Ipv6Addr,
+	SocketAddr,
+	SocketAddrV4,
+	SocketAddrV6,
+};
+
+/// Writes a socket address in its 6-byte wire layout.
+pub fn v4_to_bytes(addr: SocketAddrV4, order: Endianness) -> [u8; 6] {
+	let mut out = [0; 6];
+	out[.. 4].copy_from_slice(&addr.ip().octets());
+	out[4 ..].copy_from_slice(&order.convert_to(addr.port()).to_ne_bytes());
+	out
+}
+
+/// Reads a socket address from its 6-byte wire layout.
+pub fn v4_from_bytes(bytes: [u8; 6], order: Endianness) -> SocketAddrV4 {
+	let [a, b, c, d, p0, p1] = bytes;
+	SocketAddrV4::new(
+		Ipv4Addr::new(a, b, c, d),
+		order.convert_from(u16::from_ne_bytes([p0, p1])),
+	)
+}
+
+/// Writes a socket address in its 26-byte wire layout.
+pub fn v6_to_bytes(addr: SocketAddrV6, order: Endianness) -> [u8; 26] {
+	let mut out = [0; 26];
+	out[.. 16].copy_from_slice(&addr.ip().octets());
+	out[16 .. 18].copy_from_slice(&order.convert_to(addr.port()).to_ne_bytes());
+	out[18 .. 22]
+		.copy_from_slice(&order.convert_to(addr.flowinfo()).to_ne_bytes());
+	out[22 ..].copy_from_slice(&order.convert_to(addr.scope_id()).to_ne_bytes());
+	out
+}
+
+/// Reads a socket address from its 26-byte wire layout.
+pub fn v6_from_bytes(bytes: [u8; 26], order: Endianness) -> SocketAddrV6 {
+	let mut octets = [0; 16];
+	octets.copy_from_slice(&bytes[.. 16]);
+	let mut port = [0; 2];
+	port.copy_from_slice(&bytes[16 .. 18]);
+	let mut flowinfo = [0; 4];
+	flowinfo.copy_from_slice(&bytes[18 .. 22]);
+	let mut scope_id = [0; 4];
+	scope_id.copy_from_slice(&bytes[22 ..]);
+	SocketAddrV6::new(
+		Ipv6Addr::from(octets),
+		order.convert_from(u16::from_ne_bytes(port)),
+		order.convert_from(u32::from_ne_bytes(flowinfo)),
+		order.convert_from(u32::from_ne_bytes(scope_id)),
+	)
+}
+
+/// Implement Endian on types that are already in network order.
+macro_rules! implendian_net {
+	( $( $t:ty ),* ) => { $(
+		impl Endian for $t {
+			#[inline(always)]
+			fn from_be(self) -> Self { self }
+			#[inline(always)]
+			fn from_le(self) -> Self { self }
+			#[inline(always)]
+			fn to_be(self) -> Self { self }
+			#[inline(always)]
+			fn to_le(self) -> Self { self }
+		}
+	)* };
+}
+
+implendian_net!(Ipv4Addr, Ipv6Addr, IpAddr);
+
+/// The port is converted; the address is not.
+impl Endian for SocketAddrV4 {
+	fn from_be(self) -> Self {
+		SocketAddrV4::new(*self.ip(), self.port().from_be())
+	}
+	fn from_le(self) -> Self {
+		SocketAddrV4::new(*self.ip(), self.port().from_le())
+	}
+	fn to_be(self) -> Self {
+		SocketAddrV4::new(*self.ip(), self.port().to_be())
+	}
+	fn to_le(self) -> Self {
+		SocketAddrV4::new(*self.ip(), self.port().to_le())
+	}
+}
+
+/// The port, flow information, and scope ID are converted; the address is not.
+impl Endian for SocketAddrV6 {
+	fn from_be(self) -> Self {
+		SocketAddrV6::new(
+			*self.ip(),
+			self.port().from_be(),
+			self.flowinfo().from_be(),
+			self.scope_id().from_be(),
+		)
+	}
+	fn from_le(self) -> Self {
+		SocketAddrV6::new(
+			*self.ip(),
+			self.port().from_le(),
+			self.flowinfo().from_le(),
+			self.scope_id().from_le(),
+		)
+	}
+	fn to_be(self) -> Self {
+		SocketAddrV6::new(
+			*self.ip(),
+			self.port().to_be(),
+			self.flowinfo().to_be(),
+			self.scope_id().to_be(),
+		)
+	}
+	fn to_le(self) -> Self {
+		SocketAddrV6::new(
+			*self.ip(),
+			self.port().to_le(),
+			self.flowinfo().to_le(),
+			self.scope_id().to_le(),
+		)
+	}
+}
+
+/// Each variant delegates to its address.
+impl Endian for SocketAddr {
+	fn from_be(self) -> Self {
+		match self {
+			SocketAddr::V4(addr) => SocketAddr::V4(addr.from_be()),
+			SocketAddr::V6(addr) => SocketAddr::V6(addr.from_be()),
+		}
+	}
+	fn from_le(self) -> Self {
+		match self {
+			SocketAddr::V4(addr) => SocketAddr::V4(addr.from_le()),
+			SocketAddr::V6(addr) => SocketAddr::V6(addr.from_le()),
+		}
+	}
+	fn to_be(self) -> Self {
+		match self {
+			SocketAddr::V4(addr) => SocketAddr::V4(addr.to_be()),
+			SocketAddr::V6(addr) => SocketAddr::V6(addr.to_be()),
+		}
+	}
+	fn to_le(self) -> Self {
+		match self {
+			SocketAddr::V4(addr) => SocketAddr::V4(addr.to_le()),
+			SocketAddr::V6(addr) => SocketAddr::V6(addr.to_le()),
+		}
+	}
+}
@@ -0,0 +1,114 @@
+/*! Parsers for `nom`
+
+`nom` provides parsers such as `be_u32` for the primitives, but a struct of them
+needs its own parser that names each field again. The `be` and `le` functions
+here build a parser for any `Endian` type that is `FromBytes`, which includes
+structs that derive `Endian` with `#[endian(from_bytes)]`. The parser takes
+`size_of::<T>()` bytes from the input, converts them to local order, and
+returns the value:
+
+```rust
+use endian_trait::Endian;
+use nom::{IResult, number::complete::be_u16, sequence::pair};
+
+#[repr(C)]
+#[derive(Clone, Copy, Endian)]
+#[endian(from_bytes)]
+struct Header {
+    kind: u16,
+    length: u16,
+}
+
+fn packet(input: &[u8]) -> IResult<&[u8], (Header, u16)> {
+    pair(endian_trait::nom::be::<Header>(), be_u16)(input)
+}
+
+let (rest, (header, body)) = packet(&[0, 1, 0, 8, 0xde, 0xad, 0xff]).unwrap();
+assert_eq!((header.kind, header.length, body), (1, 8, 0xdead));
+assert_eq!(rest, &[0xff]);
+```
+
+Like the parsers in `nom::number::complete`, they fail with `ErrorKind::Eof`
+when the input is too short.
+!*/
+
+use super::{
+	Endian,
+	Endianness,
+};
+
+use super::bytes::FromBytes;
+
+use ::nom::{
+	Err,
+	IResult,
+	Parser,
+	error::{
+		ErrorKind,
+		ParseError,
+	},
+};
+
+use core::{
+	fmt,
+	marker::PhantomData,
+	mem,
+};
+
+/// Builds a parser for a big-endian `T`.
+pub fn be<T: Endian + FromBytes>() -> Take<T> {
+	Take::new(Endianness::Big)
+}
+
+/// Builds a parser for a little-endian `T`.
+pub fn le<T: Endian + FromBytes>() -> Take<T> {
+	Take::new(Endianness::Little)
+}
+
+/** A parser that takes one `T` from the front of a byte slice.
+
+This is built by `be` and `le`, and can be used anywhere `nom` accepts a
+`Parser`.
+**/
+pub struct Take<T> {
+	order: Endianness,
+	_ty: PhantomData<fn() -> T>,
+}
+
+impl<T: Endian + FromBytes> Take<T> {
+	/// Builds a parser for a `T` in the given order.
+	pub fn new(order: Endianness) -> Self {
+		Take { order, _ty: PhantomData }
+	}
+}
+
+impl<T> Clone for Take<T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<T> Copy for Take<T> {}
+
+impl<T> fmt::Debug for Take<T> {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("Take")
+			.field("order", &self.order)
+			.field("width", &mem::size_of::<T>())
+			.finish()
+	}
+}
+
+impl<'a, T, E> Parser<&'a [u8], T, E> for Take<T>
+where T: Endian + FromBytes, E: ParseError<&'a [u8]> {
+	fn parse(&mut self, input: &'a [u8]) -> IResult<&'a [u8], T, E> {
+		let width = mem::size_of::<T>();
+		if input.len() < width {
+			return Err(Err::Error(E::from_error_kind(input, ErrorKind::Eof)));
+		}
+		let (head, rest) = input.split_at(width);
+		let value = T::read_from(head)
+			.unwrap_or_else(|_| unreachable!("`head` is exactly `T` wide"));
+		Ok((rest, self.order.convert_from(value)))
+	}
+}
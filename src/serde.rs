@@ -0,0 +1,117 @@
+/*! Field adapters for `serde`
+
+Some formats, such as CBOR, store byte strings natively, and fields like hashes
+and identifiers belong in them in a fixed byte order rather than as numbers. The
+`be` and `le` modules here serialize any `Endian` type that is `AsBytes` and
+`FromBytes` as the bytes of its big- or little-endian representation, and can be
+named in `#[serde(with = "…")]` attributes:
+
+```rust
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+struct Block {
+    #[serde(with = "endian_trait::serde::be")]
+    id: u64,
+    #[serde(with = "endian_trait::serde::le")]
+    checksum: u32,
+    height: u32,
+}
+```
+
+Deserializing accepts a byte string, or a sequence of bytes for formats that
+cannot represent byte strings, and fails if it is not exactly as wide as the
+field.
+!*/
+
+use super::Endian;
+
+use super::bytes::{
+	AsBytes,
+	FromBytes,
+};
+
+use ::serde::{
+	Deserializer,
+	Serializer,
+	de::{
+		Error,
+		SeqAccess,
+		Visitor,
+	},
+};
+
+use core::{
+	fmt,
+	marker::PhantomData,
+	mem,
+};
+
+/// Serializes values as their big-endian bytes.
+pub mod be {
+	use super::*;
+
+	/// Serializes a value as the bytes of its big-endian representation.
+	pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+	where T: Endian + AsBytes + Clone, S: Serializer {
+		serializer.serialize_bytes(value.clone().to_be().as_bytes())
+	}
+
+	/// Deserializes a value from the bytes of its big-endian representation.
+	pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+	where T: Endian + AsBytes + FromBytes, D: Deserializer<'de> {
+		deserializer.deserialize_bytes(BytesVisitor(PhantomData))
+			.map(Endian::from_be)
+	}
+}
+
+/// Serializes values as their little-endian bytes.
+pub mod le {
+	use super::*;
+
+	/// Serializes a value as the bytes of its little-endian representation.
+	pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+	where T: Endian + AsBytes + Clone, S: Serializer {
+		serializer.serialize_bytes(value.clone().to_le().as_bytes())
+	}
+
+	/// Deserializes a value from the bytes of its little-endian representation.
+	pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+	where T: Endian + AsBytes + FromBytes, D: Deserializer<'de> {
+		deserializer.deserialize_bytes(BytesVisitor(PhantomData))
+			.map(Endian::from_le)
+	}
+}
+
+/// Collects the raw, unconverted, bytes of a `T`.
+struct BytesVisitor<T>(PhantomData<T>);
+
+impl<'de, T: AsBytes + FromBytes> Visitor<'de> for BytesVisitor<T> {
+	type Value = T;
+
+	fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		write!(fmt, "{} bytes", mem::size_of::<T>())
+	}
+
+	fn visit_bytes<E: Error>(self, bytes: &[u8]) -> Result<T, E> {
+		T::read_from(bytes)
+			.map_err(|_| E::invalid_length(bytes.len(), &self))
+	}
+
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+		let mut out = T::new_zeroed();
+		let len = {
+			let buf = out.as_bytes_mut();
+			for (idx, slot) in buf.iter_mut().enumerate() {
+				*slot = seq.next_element()?
+					.ok_or_else(|| A::Error::invalid_length(idx, &self))?;
+			}
+			buf.len()
+		};
+		//  Refuse sequences that run past the end of the value.
+		if seq.next_element::<u8>()?.is_some() {
+			return Err(A::Error::invalid_length(len + 1, &self));
+		}
+		Ok(out)
+	}
+}
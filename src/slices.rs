@@ -1,10 +1,16 @@
-/*! Implement `Endian` on mutable slices.
+/*! Implement `Endian` on mutable slices, and on owned sequences.
 !*/
 
 use super::Endian;
 
+#[cfg(feature = "std")]
+use std::{
+	boxed::Box,
+	vec::Vec,
+};
+
 /// Traverse a slice, performing the `Endian` method on each item in place.
-impl<'a, T: Endian> Endian for &'a mut [T] {
+impl<T: Endian> Endian for &mut [T] {
 	fn from_be(self) -> Self {
 		for elt in self.iter_mut() { unsafe {
 			let ptr = elt as *mut T;
@@ -34,3 +40,30 @@ impl<'a, T: Endian> Endian for &'a mut [T] {
 		self
 	}
 }
+
+/** Implement on owned sequences, by converting each element by value.
+
+These let the length-prefixed fields of a derived struct be `Vec<T>` or
+`Box<[T]>`.
+**/
+macro_rules! implendian_seq {
+	( $( $t:ty ),* ) => { $(
+		#[cfg(feature = "std")]
+		impl<T: Endian> Endian for $t {
+			fn from_be(self) -> Self {
+				Vec::from(self).into_iter().map(T::from_be).collect()
+			}
+			fn from_le(self) -> Self {
+				Vec::from(self).into_iter().map(T::from_le).collect()
+			}
+			fn to_be(self) -> Self {
+				Vec::from(self).into_iter().map(T::to_be).collect()
+			}
+			fn to_le(self) -> Self {
+				Vec::from(self).into_iter().map(T::to_le).collect()
+			}
+		}
+	)* };
+}
+
+implendian_seq!(Vec<T>, Box<[T]>);
@@ -0,0 +1,151 @@
+/*! Property tests and fuzz targets for `Endian` implementations
+
+Hand-written test values rarely reach the edges of a type: the smallest
+integer, a NaN with an unusual payload, or an empty slice. This module checks the laws
+that every `Endian` implementation must keep against generated values instead:
+
+- converting to an order and back from it restores the original bits, and
+- converting to the host's own order changes nothing.
+
+Values are compared by their bytes, so floats whose NaN payloads differ are not
+mistaken for equal, and ones whose payloads match are not mistaken for unequal.
+
+`assert_endian_roundtrip` and `assert_slice_roundtrip` check a type with values
+from its `proptest` strategy, and shrink any failure to a minimal example. The
+`fuzz_*` functions check values built from raw fuzzer input by
+`arbitrary::Arbitrary`, for use in `cargo fuzz` targets:
+
+```rust,ignore
+fuzz_target!(|data: &[u8]| {
+    let _ = endian_trait::testing::fuzz_endian_roundtrip::<MyHeader>(data);
+});
+```
+
+Checks report failures by panicking, so that test harnesses and fuzzers both
+catch them.
+
+`char` is not checked here: most `char`s have no valid converted form, and the
+checks would have to handle the invalid ones. Check its `u32` value instead.
+!*/
+
+use super::{
+	Endian,
+	Endianness,
+};
+
+use super::bytes::AsBytes;
+
+use arbitrary::Unstructured;
+
+use proptest::{
+	arbitrary::any,
+	collection::vec,
+	test_runner::TestRunner,
+};
+
+use std::{
+	fmt::Debug,
+	vec::Vec,
+};
+
+/// Checks the `Endian` laws for a type, with values from its `proptest`
+/// strategy.
+pub fn assert_endian_roundtrip<T>()
+where T: Endian + AsBytes + Clone + Debug + proptest::arbitrary::Arbitrary {
+	run(&any::<T>(), |value| check_roundtrip(&value));
+}
+
+/// Checks the `Endian` laws for slices of a type, with vectors from the type's
+/// `proptest` strategy.
+pub fn assert_slice_roundtrip<T>()
+where T: Endian + AsBytes + Clone + Debug + proptest::arbitrary::Arbitrary {
+	run(&vec(any::<T>(), 0 .. 64), |values| check_slice(&values));
+}
+
+/// Checks the `Endian` laws for a value built from fuzzer input.
+///
+/// This only fails if the input is too short to build a value.
+pub fn fuzz_endian_roundtrip<'a, T>(data: &'a [u8]) -> arbitrary::Result<()>
+where T: Endian + AsBytes + Clone + Debug + arbitrary::Arbitrary<'a> {
+	let value = <T as arbitrary::Arbitrary>::arbitrary_take_rest(
+		Unstructured::new(data),
+	)?;
+	check_roundtrip(&value);
+	Ok(())
+}
+
+/// Checks the `Endian` laws for a slice built from fuzzer input.
+pub fn fuzz_slice_roundtrip<'a, T>(data: &'a [u8]) -> arbitrary::Result<()>
+where T: Endian + AsBytes + Clone + Debug + arbitrary::Arbitrary<'a> {
+	let values = <Vec<T> as arbitrary::Arbitrary>::arbitrary_take_rest(
+		Unstructured::new(data),
+	)?;
+	check_slice(&values);
+	Ok(())
+}
+
+/// Checks the `Endian` laws for one value.
+pub fn check_roundtrip<T>(value: &T)
+where T: Endian + AsBytes + Clone + Debug {
+	for order in [Endianness::Big, Endianness::Little] {
+		//  A converted value need not be valid as its own type, so it is only
+		//  ever moved back, and reported by its bytes.
+		let there = order.convert_to(value.clone());
+		let bytes = there.as_bytes().to_vec();
+		let back = order.convert_from(there);
+		assert_eq!(
+			back.as_bytes(),
+			value.as_bytes(),
+			"{:?} did not survive conversion to {:?} endian and back, by way \
+			of {:02X?}",
+			value,
+			order,
+			bytes,
+		);
+		if order == Endianness::NATIVE {
+			assert_eq!(
+				bytes,
+				value.as_bytes(),
+				"converting {:?} to the native order changed it",
+				value,
+			);
+		}
+	}
+}
+
+/// Checks the `Endian` laws for the implementation on mutable slices.
+pub fn check_slice<T>(values: &[T])
+where T: Endian + AsBytes + Clone + Debug {
+	for order in [Endianness::Big, Endianness::Little] {
+		let mut copy = values.to_vec();
+		order.convert_from(order.convert_to(&mut copy[..]));
+		assert_eq!(
+			copy.as_slice().as_bytes(),
+			values.as_bytes(),
+			"{:?} did not survive conversion to {:?} endian and back",
+			values,
+			order,
+		);
+		//  Each element converts on its own, as if it were alone.
+		let mut copy = values.to_vec();
+		order.convert_to(&mut copy[..]);
+		for (elt, orig) in copy.iter().zip(values) {
+			let alone = order.convert_to(orig.clone());
+			assert_eq!(elt.as_bytes(), alone.as_bytes());
+		}
+	}
+}
+
+/// Runs a property over a strategy, and panics with the minimal failure.
+fn run<S: proptest::strategy::Strategy>(
+	strategy: &S,
+	check: impl Fn(S::Value),
+) {
+	let mut runner = TestRunner::default();
+	if let Err(err) = runner.run(strategy, |value| {
+		check(value);
+		Ok(())
+	}) {
+		panic!("{}", err);
+	}
+}
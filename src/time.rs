@@ -0,0 +1,115 @@
+/*! A wire layout for `Duration`
+
+`core::time::Duration` does not promise any particular layout, so it cannot be
+converted in place or sent as bytes. `WireDuration` is a plain `repr(C)` record
+of the same information, a `u64` count of whole seconds followed by a `u32`
+count of nanoseconds, which implements `Endian` and converts to and from
+`Duration`.
+
+Struct fields of type `Duration` can be marked `#[endian(as = WireDuration)]`.
+`#[derive(Endian)]` then leaves the field as it is, and `#[derive(Codec)]`
+encodes it as a `WireDuration`:
+
+```rust
+# #[cfg(feature = "std")] {
+use core::time::Duration;
+use endian_trait::{Codec, Endian, codec, time::WireDuration};
+
+#[derive(Clone, Codec, Endian)]
+struct Telemetry {
+    sensor: u16,
+    #[endian(as = WireDuration)]
+    timeout: Duration,
+}
+
+let record = Telemetry { sensor: 1, timeout: Duration::from_millis(1500) };
+let mut wire = Vec::new();
+codec::encode_be(&record, &mut wire).unwrap();
+assert_eq!(wire, [0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0x1D, 0xCD, 0x65, 0]);
+# }
+```
+!*/
+
+use super::Endian;
+
+use core::{
+	convert::TryFrom,
+	fmt,
+	time::Duration,
+};
+
+/// The number of nanoseconds in a second.
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/** A `Duration`, with a fixed layout.
+
+In memory, this has four bytes of trailing padding, so it is not `AsBytes`. Its
+`Codec` form is the twelve bytes of its two fields, with no padding.
+**/
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct WireDuration {
+	/// The number of whole seconds.
+	pub secs: u64,
+	/// The number of nanoseconds past `secs`. A valid duration keeps this below
+	/// one billion.
+	pub nanos: u32,
+}
+
+impl Endian for WireDuration {
+	fn from_be(self) -> Self {
+		WireDuration {
+			secs: self.secs.from_be(),
+			nanos: self.nanos.from_be(),
+		}
+	}
+	fn from_le(self) -> Self {
+		WireDuration {
+			secs: self.secs.from_le(),
+			nanos: self.nanos.from_le(),
+		}
+	}
+	fn to_be(self) -> Self {
+		WireDuration {
+			secs: self.secs.to_be(),
+			nanos: self.nanos.to_be(),
+		}
+	}
+	fn to_le(self) -> Self {
+		WireDuration {
+			secs: self.secs.to_le(),
+			nanos: self.nanos.to_le(),
+		}
+	}
+}
+
+impl From<Duration> for WireDuration {
+	fn from(dur: Duration) -> Self {
+		WireDuration {
+			secs: dur.as_secs(),
+			nanos: dur.subsec_nanos(),
+		}
+	}
+}
+
+/// This fails if `nanos` is a second or more.
+impl TryFrom<WireDuration> for Duration {
+	type Error = DurationError;
+
+	fn try_from(wire: WireDuration) -> Result<Self, Self::Error> {
+		if wire.nanos >= NANOS_PER_SEC {
+			return Err(DurationError);
+		}
+		Ok(Duration::new(wire.secs, wire.nanos))
+	}
+}
+
+/// The error for a `WireDuration` whose nanoseconds are a second or more.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DurationError;
+
+impl fmt::Display for DurationError {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.write_str("the nanoseconds of a duration must be less than a second")
+	}
+}
@@ -0,0 +1,172 @@
+/*! Storage for values that may not be aligned
+
+Wire formats often place values at offsets that their type's alignment does not
+allow, and `#[repr(packed)]` structs that mirror those formats cannot safely
+hand out references to their fields. `Unaligned<T>` holds the bytes of a `T` with
+an alignment of 1, so it can sit at any offset, and only ever reads and writes
+its value through `read_unaligned` and `write_unaligned`.
+
+```rust
+use endian_trait::{Endian, Unaligned};
+
+#[repr(C)]
+#[derive(Clone, Copy, Endian)]
+#[endian(from_bytes, size = 7)]
+struct Header {
+    kind: u8,
+    length: Unaligned<u32>,
+    checksum: Unaligned<u16>,
+}
+
+let mut header = Header {
+    kind: 1,
+    length: Unaligned::new(0),
+    checksum: Unaligned::new(0),
+};
+header.length.set_be(0x0102_0304);
+assert_eq!(header.length.get().to_ne_bytes(), [1, 2, 3, 4]);
+assert_eq!(header.length.get_be(), 0x0102_0304);
+```
+!*/
+
+use super::Endian;
+
+use super::bytes::{
+	AsBytes,
+	FromBytes,
+};
+
+use core::{
+	cmp,
+	fmt,
+	hash,
+	ptr,
+};
+
+/** A `T` stored with an alignment of 1.
+
+This has the same size as `T`, but may be placed at any address. Its value can
+be copied out with `get`, or replaced with `set`; the `_be` and `_le` forms of
+each also convert it between host endian and a fixed order.
+
+It is a packed `T` rather than an array of `T`'s bytes, as an array's length
+cannot yet be computed from a generic `T`. Packing gives it the same bytes and
+an alignment of 1, and no reference to the inner `T` is ever made.
+**/
+#[repr(C, packed)]
+pub struct Unaligned<T>(T);
+
+impl<T: Copy> Unaligned<T> {
+	/// Stores a value.
+	pub fn new(value: T) -> Self {
+		Unaligned(value)
+	}
+
+	/// Copies the stored value out.
+	pub fn get(&self) -> T {
+		unsafe { ptr::read_unaligned(ptr::addr_of!(self.0)) }
+	}
+
+	/// Replaces the stored value.
+	pub fn set(&mut self, value: T) {
+		unsafe { ptr::write_unaligned(ptr::addr_of_mut!(self.0), value) }
+	}
+
+	/// Copies the stored value out.
+	pub fn into_inner(self) -> T {
+		self.get()
+	}
+}
+
+impl<T: Copy + Endian> Unaligned<T> {
+	/// Copies out a stored big-endian value, and converts it to host endian.
+	pub fn get_be(&self) -> T {
+		self.get().from_be()
+	}
+
+	/// Copies out a stored little-endian value, and converts it to host endian.
+	pub fn get_le(&self) -> T {
+		self.get().from_le()
+	}
+
+	/// Converts a value to big-endian, and stores it.
+	pub fn set_be(&mut self, value: T) {
+		self.set(value.to_be())
+	}
+
+	/// Converts a value to little-endian, and stores it.
+	pub fn set_le(&mut self, value: T) {
+		self.set(value.to_le())
+	}
+}
+
+impl<T: Copy + Endian> Endian for Unaligned<T> {
+	fn from_be(self) -> Self {
+		Self::new(self.get().from_be())
+	}
+	fn from_le(self) -> Self {
+		Self::new(self.get().from_le())
+	}
+	fn to_be(self) -> Self {
+		Self::new(self.get().to_be())
+	}
+	fn to_le(self) -> Self {
+		Self::new(self.get().to_le())
+	}
+}
+
+//  Packing removes only the outer alignment; the bytes are those of `T`.
+unsafe impl<T: AsBytes> AsBytes for Unaligned<T> {}
+unsafe impl<T: FromBytes> FromBytes for Unaligned<T> {}
+
+impl<T: Copy> Clone for Unaligned<T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<T: Copy> Copy for Unaligned<T> {}
+
+impl<T: Copy + Default> Default for Unaligned<T> {
+	fn default() -> Self {
+		Self::new(T::default())
+	}
+}
+
+impl<T: Copy + fmt::Debug> fmt::Debug for Unaligned<T> {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_tuple("Unaligned").field(&self.get()).finish()
+	}
+}
+
+impl<T: Copy + PartialEq> PartialEq for Unaligned<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.get() == other.get()
+	}
+}
+
+impl<T: Copy + Eq> Eq for Unaligned<T> {}
+
+impl<T: Copy + PartialOrd> PartialOrd for Unaligned<T> {
+	fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+		self.get().partial_cmp(&other.get())
+	}
+}
+
+impl<T: Copy + Ord> Ord for Unaligned<T> {
+	fn cmp(&self, other: &Self) -> cmp::Ordering {
+		self.get().cmp(&other.get())
+	}
+}
+
+impl<T: Copy + hash::Hash> hash::Hash for Unaligned<T> {
+	fn hash<H: hash::Hasher>(&self, state: &mut H) {
+		self.get().hash(state)
+	}
+}
+
+impl<T: Copy> From<T> for Unaligned<T> {
+	fn from(value: T) -> Self {
+		Self::new(value)
+	}
+}
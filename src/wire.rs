@@ -0,0 +1,357 @@
+/*! Fixed-size wire layouts, independent of Rust's layout
+
+`#[repr(C)]` structs have the padding that their fields' alignment requires,
+while wire formats rarely do. The `Wire` trait describes a value's wire form
+separately from its memory: a fixed number of bytes, `WIRE_SIZE`, which it
+writes into and reads from a byte slice in either order.
+
+`#[derive(Endian)]` implements `Wire` on structs and enums marked
+`#[endian(wire)]`. A struct's wire form is its fields in declaration order, with
+nothing between them, so the struct can keep its natural alignment in memory:
+
+```rust
+use endian_trait::{Endian, Wire};
+
+#[derive(Debug, Endian, PartialEq)]
+#[endian(wire)]
+struct Reading {
+    sensor: u8,
+    value: i32,
+    valid: bool,
+}
+
+assert_eq!(core::mem::size_of::<Reading>(), 8);
+assert_eq!(Reading::WIRE_SIZE, 6);
+
+let reading = Reading { sensor: 3, value: -2, valid: true };
+let mut buf = [0; Reading::WIRE_SIZE];
+reading.write_be_into(&mut buf);
+assert_eq!(buf, [3, 0xFF, 0xFF, 0xFF, 0xFE, 1]);
+assert_eq!(Reading::read_be_from(&buf), reading);
+```
+
+The buffer passed to each method must be at least `WIRE_SIZE` bytes long, and
+only its first `WIRE_SIZE` bytes are used. The methods panic on shorter buffers,
+and reading panics on bytes that are not a valid value, such as a `bool` byte
+other than `0` or `1`. Use `Codec` where the input is untrusted.
+!*/
+
+use super::{
+	Endianness,
+	Unaligned,
+	net,
+};
+
+use super::bytes::{
+	AsBytes,
+	FromBytes,
+};
+
+use super::time::WireDuration;
+
+use core::{
+	array,
+	mem,
+	net::{
+		Ipv4Addr,
+		Ipv6Addr,
+		SocketAddrV4,
+		SocketAddrV6,
+	},
+};
+
+/** A value with a fixed-size wire form.
+
+This is implemented on the primitives, arrays of `Wire` types, `Unaligned`,
+`WireDuration`, and the `core::net` address types, and by `#[derive(Endian)]` on
+types marked `#[endian(wire)]`.
+**/
+pub trait Wire: Sized {
+	/// The number of bytes in the wire form.
+	const WIRE_SIZE: usize;
+
+	/// Writes the value into the front of `buf`, in the given order.
+	fn write_wire(&self, order: Endianness, buf: &mut [u8]);
+
+	/// Reads a value from the front of `buf`, which is in the given order.
+	fn read_wire(order: Endianness, buf: &[u8]) -> Self;
+
+	/// Writes the value into the front of `buf`, in big-endian order.
+	fn write_be_into(&self, buf: &mut [u8]) {
+		self.write_wire(Endianness::Big, buf)
+	}
+
+	/// Writes the value into the front of `buf`, in little-endian order.
+	fn write_le_into(&self, buf: &mut [u8]) {
+		self.write_wire(Endianness::Little, buf)
+	}
+
+	/// Reads a big-endian value from the front of `buf`.
+	fn read_be_from(buf: &[u8]) -> Self {
+		Self::read_wire(Endianness::Big, buf)
+	}
+
+	/// Reads a little-endian value from the front of `buf`.
+	fn read_le_from(buf: &[u8]) -> Self {
+		Self::read_wire(Endianness::Little, buf)
+	}
+}
+
+/// Writes a value into the front of a buffer, and moves the buffer past it.
+///
+/// This is the building block of a struct's `write_wire`.
+pub fn put<T: Wire>(value: &T, order: Endianness, buf: &mut &mut [u8]) {
+	let (head, rest) = mem::take(buf).split_at_mut(T::WIRE_SIZE);
+	value.write_wire(order, head);
+	*buf = rest;
+}
+
+/// Reads a value from the front of a buffer, and moves the buffer past it.
+///
+/// This is the building block of a struct's `read_wire`.
+pub fn take<T: Wire>(order: Endianness, buf: &mut &[u8]) -> T {
+	let (head, rest) = buf.split_at(T::WIRE_SIZE);
+	*buf = rest;
+	T::read_wire(order, head)
+}
+
+/// Fills `len` bytes at the front of a buffer with zeros, and moves the buffer
+/// past them.
+///
+/// This writes the padding and reserved bytes of a struct's wire form.
+pub fn pad(len: usize, buf: &mut &mut [u8]) {
+	let (head, rest) = mem::take(buf).split_at_mut(len);
+	head.fill(0);
+	*buf = rest;
+}
+
+/// Moves a buffer past `len` bytes at its front, and returns them.
+///
+/// This passes over the padding and reserved bytes of a struct's wire form.
+pub fn skip<'a>(len: usize, buf: &mut &'a [u8]) -> &'a [u8] {
+	let (head, rest) = buf.split_at(len);
+	*buf = rest;
+	head
+}
+
+/// Implement Wire on types whose wire form is their converted bytes.
+macro_rules! implwire {
+	( $( $t:ty ),* ) => { $(
+		impl Wire for $t {
+			const WIRE_SIZE: usize = mem::size_of::<$t>();
+
+			fn write_wire(&self, order: Endianness, buf: &mut [u8]) {
+				buf[.. Self::WIRE_SIZE]
+					.copy_from_slice(order.convert_to(*self).as_bytes());
+			}
+
+			fn read_wire(order: Endianness, buf: &[u8]) -> Self {
+				let mut out = Self::new_zeroed();
+				out.as_bytes_mut().copy_from_slice(&buf[.. Self::WIRE_SIZE]);
+				order.convert_from(out)
+			}
+		}
+	)* };
+}
+
+implwire!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, f32, f64);
+
+/// `bool` is a single byte, which must be `0` or `1`.
+impl Wire for bool {
+	const WIRE_SIZE: usize = 1;
+
+	fn write_wire(&self, order: Endianness, buf: &mut [u8]) {
+		(*self as u8).write_wire(order, buf)
+	}
+
+	fn read_wire(order: Endianness, buf: &[u8]) -> Self {
+		match u8::read_wire(order, buf) {
+			0 => false,
+			1 => true,
+			n => panic!("A `bool` cannot have a value of {:X}", n),
+		}
+	}
+}
+
+/// `char` is its scalar value, as a `u32`.
+///
+/// Like `Endian::from_be`, reading panics on an invalid scalar value.
+impl Wire for char {
+	const WIRE_SIZE: usize = 4;
+
+	fn write_wire(&self, order: Endianness, buf: &mut [u8]) {
+		(*self as u32).write_wire(order, buf)
+	}
+
+	fn read_wire(order: Endianness, buf: &[u8]) -> Self {
+		let n = u32::read_wire(order, buf);
+		core::char::from_u32(n)
+			.unwrap_or_else(|| panic!("A `char` cannot have a value of {:X}", n))
+	}
+}
+
+/// Arrays are each of their elements in turn.
+impl<T: Wire, const N: usize> Wire for [T; N] {
+	const WIRE_SIZE: usize = T::WIRE_SIZE * N;
+
+	fn write_wire(&self, order: Endianness, mut buf: &mut [u8]) {
+		for elt in self {
+			put(elt, order, &mut buf);
+		}
+	}
+
+	fn read_wire(order: Endianness, mut buf: &[u8]) -> Self {
+		array::from_fn(|_| take(order, &mut buf))
+	}
+}
+
+/// An unaligned value has the wire form of the value.
+impl<T: Copy + Wire> Wire for Unaligned<T> {
+	const WIRE_SIZE: usize = T::WIRE_SIZE;
+
+	fn write_wire(&self, order: Endianness, buf: &mut [u8]) {
+		self.get().write_wire(order, buf)
+	}
+
+	fn read_wire(order: Endianness, buf: &[u8]) -> Self {
+		Unaligned::new(T::read_wire(order, buf))
+	}
+}
+
+/// Durations are their seconds, then their nanoseconds, with no padding.
+impl Wire for WireDuration {
+	const WIRE_SIZE: usize = 12;
+
+	fn write_wire(&self, order: Endianness, mut buf: &mut [u8]) {
+		put(&self.secs, order, &mut buf);
+		put(&self.nanos, order, &mut buf);
+	}
+
+	fn read_wire(order: Endianness, mut buf: &[u8]) -> Self {
+		WireDuration {
+			secs: take(order, &mut buf),
+			nanos: take(order, &mut buf),
+		}
+	}
+}
+
+/// IPv4 addresses are their 4 octets.
+impl Wire for Ipv4Addr {
+	const WIRE_SIZE: usize = 4;
+
+	fn write_wire(&self, _: Endianness, buf: &mut [u8]) {
+		buf[.. 4].copy_from_slice(&self.octets())
+	}
+
+	fn read_wire(order: Endianness, buf: &[u8]) -> Self {
+		Self::from(<[u8; 4]>::read_wire(order, buf))
+	}
+}
+
+/// IPv6 addresses are their 16 octets.
+impl Wire for Ipv6Addr {
+	const WIRE_SIZE: usize = 16;
+
+	fn write_wire(&self, _: Endianness, buf: &mut [u8]) {
+		buf[.. 16].copy_from_slice(&self.octets())
+	}
+
+	fn read_wire(order: Endianness, buf: &[u8]) -> Self {
+		Self::from(<[u8; 16]>::read_wire(order, buf))
+	}
+}
+
+/// IPv4 socket addresses use the layout of `net::v4_to_bytes`.
+impl Wire for SocketAddrV4 {
+	const WIRE_SIZE: usize = 6;
+
+	fn write_wire(&self, order: Endianness, buf: &mut [u8]) {
+		buf[.. 6].copy_from_slice(&net::v4_to_bytes(*self, order))
+	}
+
+	fn read_wire(order: Endianness, buf: &[u8]) -> Self {
+		net::v4_from_bytes(<[u8; 6]>::read_wire(Endianness::NATIVE, buf), order)
+	}
+}
+
+/// IPv6 socket addresses use the layout of `net::v6_to_bytes`.
+impl Wire for SocketAddrV6 {
+	const WIRE_SIZE: usize = 26;
+
+	fn write_wire(&self, order: Endianness, buf: &mut [u8]) {
+		buf[.. 26].copy_from_slice(&net::v6_to_bytes(*self, order))
+	}
+
+	fn read_wire(order: Endianness, buf: &[u8]) -> Self {
+		net::v6_from_bytes(<[u8; 26]>::read_wire(Endianness::NATIVE, buf), order)
+	}
+}
+
+/** Conversion to and from the few bits of a bit field.
+
+The derives use this for fields marked `#[endian(bits = N)]`. It is
+implemented on the integers up to 64 bits, and on `bool`. Signed integers are
+stored in two's complement, and sign-extended when they are decoded.
+**/
+pub trait Bits: Copy {
+	/// Converts the value to its low `width` bits, or `None` if it does not fit
+	/// in that many bits.
+	fn to_bits(self, width: u32) -> Option<u64>;
+
+	/// Converts the low `width` bits of `bits` to a value.
+	fn from_bits(bits: u64, width: u32) -> Self;
+}
+
+/// Implement Bits on the unsigned integers.
+macro_rules! implbits_u {
+	( $( $t:ty ),* ) => { $(
+		impl Bits for $t {
+			fn to_bits(self, width: u32) -> Option<u64> {
+				let bits = self as u64;
+				if width < 64 && bits >> width != 0 {
+					return None;
+				}
+				Some(bits)
+			}
+
+			fn from_bits(bits: u64, _: u32) -> Self {
+				bits as Self
+			}
+		}
+	)* };
+}
+
+/// Implement Bits on the signed integers.
+macro_rules! implbits_i {
+	( $( $t:ty ),* ) => { $(
+		impl Bits for $t {
+			fn to_bits(self, width: u32) -> Option<u64> {
+				let wide = self as i64;
+				if width < 64 && (wide >> (width - 1)) != (wide >> 63) {
+					return None;
+				}
+				Some(wide as u64 & (u64::MAX >> (64 - width)))
+			}
+
+			fn from_bits(bits: u64, width: u32) -> Self {
+				//  Move the field's sign bit to the top, then shift it back down
+				//  to copy it through the high bits.
+				let spare = 64 - width;
+				(((bits << spare) as i64) >> spare) as Self
+			}
+		}
+	)* };
+}
+
+implbits_u!(u8, u16, u32, u64);
+implbits_i!(i8, i16, i32, i64);
+
+impl Bits for bool {
+	fn to_bits(self, _: u32) -> Option<u64> {
+		Some(self as u64)
+	}
+
+	fn from_bits(bits: u64, _: u32) -> Self {
+		bits != 0
+	}
+}
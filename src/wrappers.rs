@@ -0,0 +1,69 @@
+/*! Implement `Endian` on the standard library's single-value wrappers.
+
+Each of these holds exactly one `T`, and converting the wrapper is converting
+that value. Structs that use them for interior mutability, drop control, or
+arithmetic behavior can derive `Endian` as usual.
+
+`Wrapping` and `Saturating` also have unstable inherent methods named `to_be`,
+`from_be`, and so on, so calling these on them directly triggers a lint about
+future name collisions. Call them through the trait, as `Endian::to_be(value)`,
+instead. Derived implementations already do this.
+!*/
+
+use super::Endian;
+
+use core::{
+	cell::{
+		Cell,
+		UnsafeCell,
+	},
+	cmp::Reverse,
+	mem::ManuallyDrop,
+	num::{
+		Saturating,
+		Wrapping,
+	},
+};
+
+/// Implement Endian on wrappers built with `new` and opened with `into_inner`.
+macro_rules! implendian_cell {
+	( $( $w:ident ),* ) => { $(
+		impl<T: Endian> Endian for $w<T> {
+			fn from_be(self) -> Self {
+				$w::new($w::into_inner(self).from_be())
+			}
+			fn from_le(self) -> Self {
+				$w::new($w::into_inner(self).from_le())
+			}
+			fn to_be(self) -> Self {
+				$w::new($w::into_inner(self).to_be())
+			}
+			fn to_le(self) -> Self {
+				$w::new($w::into_inner(self).to_le())
+			}
+		}
+	)* };
+}
+
+/// Implement Endian on tuple-struct wrappers with a public field.
+macro_rules! implendian_tuple {
+	( $( $w:ident ),* ) => { $(
+		impl<T: Endian> Endian for $w<T> {
+			fn from_be(self) -> Self {
+				$w(self.0.from_be())
+			}
+			fn from_le(self) -> Self {
+				$w(self.0.from_le())
+			}
+			fn to_be(self) -> Self {
+				$w(self.0.to_be())
+			}
+			fn to_le(self) -> Self {
+				$w(self.0.to_le())
+			}
+		}
+	)* };
+}
+
+implendian_cell!(Cell, UnsafeCell, ManuallyDrop);
+implendian_tuple!(Reverse, Saturating, Wrapping);
@@ -0,0 +1,81 @@
+/*! Interoperation with `zerocopy`
+
+`zerocopy`'s `byteorder` types, such as `U32<BigEndian>`, store their value in
+a fixed byte order at all times, and convert only when it is read or written
+through `.get()` and `.set()`. Their bytes are therefore already in transport
+order, and the `Endian` conversions on them are no-ops. This lets them appear as
+fields in structs that derive `Endian`, alongside fields that do need
+converting.
+
+They are also `AsBytes` and `FromBytes`, so those structs can still be marked
+`#[endian(from_bytes)]`.
+
+For the other direction, `read_be` and `read_le` copy any `zerocopy::FromBytes`
+type that also implements `Endian` out of a byte buffer and convert it.
+!*/
+
+use super::Endian;
+
+use super::bytes::{
+	AsBytes,
+	CastError,
+	FromBytes,
+};
+
+use ::zerocopy::byteorder::{
+	ByteOrder,
+	F32,
+	F64,
+	I16,
+	I32,
+	I64,
+	I128,
+	U16,
+	U32,
+	U64,
+	U128,
+};
+
+/// Copies a big-endian value out of a byte buffer with `zerocopy`, and converts
+/// it to local order.
+///
+/// The buffer must be exactly as wide as `T`, but need not be aligned.
+pub fn read_be<T>(bytes: &[u8]) -> Result<T, CastError>
+where T: ::zerocopy::FromBytes + Endian {
+	T::read_from_bytes(bytes)
+		.map(Endian::from_be)
+		.map_err(|_| CastError::Length)
+}
+
+/// Copies a little-endian value out of a byte buffer with `zerocopy`, and
+/// converts it to local order.
+///
+/// The buffer must be exactly as wide as `T`, but need not be aligned.
+pub fn read_le<T>(bytes: &[u8]) -> Result<T, CastError>
+where T: ::zerocopy::FromBytes + Endian {
+	T::read_from_bytes(bytes)
+		.map(Endian::from_le)
+		.map_err(|_| CastError::Length)
+}
+
+/// Implement Endian on the fixed-order types as no-ops.
+macro_rules! implendian_z {
+	( $( $t:ident ),* ) => { $(
+		impl<O: ByteOrder> Endian for $t<O> {
+			#[inline(always)]
+			fn from_be(self) -> Self { self }
+			#[inline(always)]
+			fn from_le(self) -> Self { self }
+			#[inline(always)]
+			fn to_be(self) -> Self { self }
+			#[inline(always)]
+			fn to_le(self) -> Self { self }
+		}
+
+		//  These are byte arrays with no padding and no invalid values.
+		unsafe impl<O: ByteOrder> AsBytes for $t<O> {}
+		unsafe impl<O: ByteOrder> FromBytes for $t<O> {}
+	)* };
+}
+
+implendian_z!(U16, U32, U64, U128, I16, I32, I64, I128, F32, F64);
@@ -0,0 +1,80 @@
+/*! Test `Endian` on the atomic integers
+!*/
+
+extern crate endian_trait;
+
+use endian_trait::{
+	AtomicEndian,
+	Endian,
+};
+use std::sync::atomic::{
+	AtomicI16,
+	AtomicU32,
+	AtomicU64,
+	AtomicUsize,
+	Ordering,
+};
+
+//  A shared-memory ring header, which another process reads in big-endian.
+#[derive(Debug, Endian)]
+struct RingHeader {
+	head: AtomicU32,
+	tail: AtomicU32,
+	generation: AtomicU64,
+}
+
+#[test]
+fn by_value() {
+	let hdr = RingHeader {
+		head: AtomicU32::new(0x01020304),
+		tail: AtomicU32::new(5),
+		generation: AtomicU64::new(0x0102),
+	}.to_be();
+	assert_eq!(hdr.head.load(Ordering::Relaxed).to_ne_bytes(), [1, 2, 3, 4]);
+	assert_eq!(hdr.tail.load(Ordering::Relaxed).to_ne_bytes(), [0, 0, 0, 5]);
+	assert_eq!(
+		hdr.generation.load(Ordering::Relaxed).to_ne_bytes(),
+		[0, 0, 0, 0, 0, 0, 1, 2],
+	);
+
+	let hdr = hdr.from_be();
+	assert_eq!(hdr.head.into_inner(), 0x01020304);
+	assert_eq!(hdr.generation.into_inner(), 0x0102);
+}
+
+#[test]
+fn by_ref() {
+	let mut val = AtomicI16::new(-2);
+	(&mut val).to_le();
+	assert_eq!(val.load(Ordering::Relaxed).to_ne_bytes(), [0xFE, 0xFF]);
+	(&mut val).from_le();
+	assert_eq!(val.into_inner(), -2);
+}
+
+#[test]
+fn load_store() {
+	let val = AtomicU32::new(0);
+	val.store_be(0xdeadbeef, Ordering::SeqCst);
+	assert_eq!(
+		val.load(Ordering::SeqCst).to_ne_bytes(),
+		[0xde, 0xad, 0xbe, 0xef],
+	);
+	assert_eq!(val.load_be(Ordering::SeqCst), 0xdeadbeef);
+
+	val.store_le(0xdeadbeef, Ordering::SeqCst);
+	assert_eq!(
+		val.load(Ordering::SeqCst).to_ne_bytes(),
+		[0xef, 0xbe, 0xad, 0xde],
+	);
+	assert_eq!(val.load_le(Ordering::SeqCst), 0xdeadbeef);
+}
+
+#[test]
+fn pointer_width() {
+	let val = AtomicUsize::new(0);
+	val.store_be(0x0102, Ordering::SeqCst);
+	assert_eq!(val.load(Ordering::SeqCst), 0x0102usize.to_be());
+	assert_eq!(val.load_be(Ordering::SeqCst), 0x0102);
+	assert_eq!(AtomicUsize::new(7).to_le().into_inner(), 7usize.to_le());
+	assert_eq!((-2isize).to_be().from_be(), -2);
+}
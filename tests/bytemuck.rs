@@ -0,0 +1,68 @@
+/*! Test casting between bytes and `bytemuck::Pod` types with conversion
+!*/
+
+#![cfg(feature = "bytemuck")]
+
+extern crate bytemuck;
+extern crate endian_trait;
+
+use bytemuck::{
+	Pod,
+	PodCastError,
+	Zeroable,
+};
+use endian_trait::Endian;
+use endian_trait::bytemuck::{
+	cast_slice_from_be,
+	cast_slice_from_le,
+	cast_slice_to_be,
+	cast_slice_to_le,
+	try_cast_slice_from_be,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Endian, Eq, PartialEq)]
+struct Sample {
+	channel: u16,
+	gain: i16,
+	value: u32,
+}
+
+unsafe impl Zeroable for Sample {}
+unsafe impl Pod for Sample {}
+
+#[test]
+fn pod_roundtrip() {
+	let samples = [
+		Sample { channel: 1, gain: -2, value: 0x01020304 },
+		Sample { channel: 0x0506, gain: 7, value: 8 },
+	];
+
+	let be = cast_slice_to_be(&samples);
+	assert_eq!(&be[.. 8], &[0, 1, 0xFF, 0xFE, 1, 2, 3, 4]);
+	assert_eq!(cast_slice_from_be::<Sample>(&be), samples);
+
+	let le = cast_slice_to_le(&samples);
+	assert_eq!(&le[8 ..], &[6, 5, 7, 0, 8, 0, 0, 0]);
+	assert_eq!(cast_slice_from_le::<Sample>(&le), samples);
+
+	//  The input need not be aligned.
+	let mut shifted = vec![0];
+	shifted.extend_from_slice(&be);
+	assert_eq!(cast_slice_from_be::<Sample>(&shifted[1 ..]), samples);
+}
+
+#[test]
+fn ragged_input() {
+	assert_eq!(
+		try_cast_slice_from_be::<u32>(&[1, 2, 3, 4, 5]),
+		Err(PodCastError::OutputSliceWouldHaveSlop),
+	);
+	assert_eq!(try_cast_slice_from_be::<u32>(&[]), Ok(vec![]));
+}
+
+#[test]
+#[should_panic(expected = "cast_slice_from_le>OutputSliceWouldHaveSlop")]
+fn ragged_input_panics() {
+	cast_slice_from_le::<u16>(&[1, 2, 3]);
+}
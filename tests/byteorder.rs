@@ -0,0 +1,81 @@
+/*! Test reading and writing `Endian` types through `byteorder`
+!*/
+
+#![cfg(feature = "byteorder")]
+
+extern crate byteorder;
+extern crate endian_trait;
+
+use byteorder::{
+	BigEndian,
+	ByteOrder,
+	LittleEndian,
+	NetworkEndian,
+	ReadBytesExt,
+};
+use endian_trait::Endian;
+use endian_trait::byteorder::{
+	EndianOrder,
+	ReadEndianExt,
+	WriteEndianExt,
+};
+use std::io;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Endian, Eq, PartialEq)]
+#[endian(from_bytes)]
+struct Entry {
+	key: u32,
+	value: i16,
+	flags: u16,
+}
+
+fn get_entry() -> Entry {
+	Entry {
+		key: 0x01020304,
+		value: -2,
+		flags: 0x8000,
+	}
+}
+
+//  A reader generic over its byte order, which uses the same parameter for the
+//  primitives it reads through `byteorder` and the structs it reads through
+//  `Endian`.
+fn read_table<O: EndianOrder>(mut src: &[u8]) -> io::Result<Vec<Entry>> {
+	let count = src.read_u16::<O>()?;
+	(0 .. count).map(|_| src.read_endian::<Entry, O>()).collect()
+}
+
+fn write_table<O: EndianOrder>(entries: &[Entry]) -> io::Result<Vec<u8>> {
+	let mut out = Vec::new();
+	out.write_endian::<_, O>(entries.len() as u16)?;
+	for entry in entries {
+		out.write_endian::<_, O>(*entry)?;
+	}
+	Ok(out)
+}
+
+#[test]
+fn generic_order() {
+	let table = [get_entry(), get_entry()];
+
+	let be = write_table::<BigEndian>(&table).unwrap();
+	assert_eq!(&be[.. 10], &[0, 2, 1, 2, 3, 4, 0xFF, 0xFE, 0x80, 0]);
+	assert_eq!(read_table::<NetworkEndian>(&be).unwrap(), table);
+
+	let le = write_table::<LittleEndian>(&table).unwrap();
+	assert_eq!(&le[.. 10], &[2, 0, 4, 3, 2, 1, 0xFE, 0xFF, 0, 0x80]);
+	assert_eq!(read_table::<LittleEndian>(&le).unwrap(), table);
+
+	assert!(read_table::<BigEndian>(&be[.. 17]).is_err());
+}
+
+#[test]
+fn matches_byteorder() {
+	let mut buf = [0; 4];
+	BigEndian::write_u32(&mut buf, 0xdeadbeef);
+	assert_eq!(BigEndian::to_order(0xdeadbeefu32).to_ne_bytes(), buf);
+	LittleEndian::write_u32(&mut buf, 0xdeadbeef);
+	assert_eq!(LittleEndian::to_order(0xdeadbeefu32).to_ne_bytes(), buf);
+	assert_eq!(LittleEndian::from_order(u32::from_ne_bytes(buf)), 0xdeadbeef);
+}
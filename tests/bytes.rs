@@ -0,0 +1,134 @@
+/*! Test zero-copy viewing of `Endian` types as bytes, and back
+!*/
+
+extern crate endian_trait;
+
+use endian_trait::{
+	Endian,
+	Endianness,
+};
+use endian_trait::bytes::{
+	self,
+	AsBytes,
+	CastError,
+	FromBytes,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Endian, Eq, PartialEq)]
+#[endian(from_bytes)]
+struct Record {
+	id: u32,
+	kind: u16,
+	flags: u16,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Endian, Eq, PartialEq)]
+#[endian(no_padding)]
+struct Flagged {
+	value: u16,
+	valid: bool,
+	kind: Kind,
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Endian, Eq, PartialEq)]
+enum Kind {
+	A = 1,
+}
+
+fn get_record() -> Record {
+	Record {
+		id: 0x01020304,
+		kind: 0x0506,
+		flags: 0x0708,
+	}
+}
+
+#[test]
+fn view_as_bytes() {
+	let be = get_record().to_be();
+	assert_eq!(be.as_bytes(), &[1, 2, 3, 4, 5, 6, 7, 8]);
+
+	let le = get_record().to_le();
+	assert_eq!(le.as_bytes(), &[4, 3, 2, 1, 6, 5, 8, 7]);
+
+	let flagged = Flagged {
+		value: 0x1234,
+		valid: true,
+		kind: Kind::A,
+	}.to_be();
+	assert_eq!(flagged.as_bytes(), &[0x12, 0x34, 1, 1]);
+}
+
+#[test]
+fn view_from_bytes() {
+	let records = [get_record().to_be(), get_record().to_be()];
+	let raw = records.as_bytes();
+
+	let one = Record::ref_from(&raw[.. 8]).unwrap();
+	assert_eq!(one.from_be(), get_record());
+
+	let all = Record::slice_from(raw).unwrap();
+	assert_eq!(all.len(), 2);
+	assert_eq!(all[1].from_be(), get_record());
+
+	assert_eq!(Record::ref_from(&raw[.. 7]), Err(CastError::Length));
+	assert_eq!(Record::slice_from(&raw[.. 12]), Err(CastError::Length));
+	assert_eq!(Record::ref_from(&raw[2 .. 10]), Err(CastError::Alignment));
+}
+
+#[test]
+fn view_mut_from_bytes() {
+	let mut raw = [0u32; 4];
+	{
+		let buf = raw.as_bytes_mut();
+		let records = Record::slice_mut_from(buf).unwrap();
+		records[1] = get_record().to_le();
+	}
+	assert_eq!(raw[2].from_le(), 0x01020304);
+	assert_eq!(Record::new_zeroed(), Record { id: 0, kind: 0, flags: 0 });
+}
+
+#[test]
+fn read_unaligned() {
+	let raw = [0xFF, 1, 2, 3, 4, 5, 6, 7, 8];
+
+	let record: Record = bytes::read_be(&raw[1 ..]).unwrap();
+	assert_eq!(record, get_record());
+
+	let id: u32 = bytes::read_le(&raw[1 .. 5]).unwrap();
+	assert_eq!(id, 0x04030201);
+
+	assert_eq!(bytes::read_be::<u32>(&raw[.. 3]), Err(CastError::Length));
+}
+
+#[test]
+fn convert_in_place() {
+	//  An aligned buffer of records.
+	let mut raw = [0u32; 4];
+	let buf = raw.as_bytes_mut();
+	buf[.. 8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+	buf[8 ..].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+	assert_eq!(bytes::convert_bytes_as::<Record>(buf, Endianness::Big), Ok(2));
+	assert_eq!(Record::slice_from(buf).unwrap(), [get_record(); 2]);
+
+	//  A misaligned buffer of words, which converts the same way.
+	let mut raw = [0u8; 13];
+	raw[1 ..].copy_from_slice(&[1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]);
+	let buf = &mut raw[1 ..];
+	assert_eq!(bytes::convert_bytes_as::<u32>(buf, Endianness::Little), Ok(3));
+	for (n, word) in buf.chunks(4).enumerate() {
+		assert_eq!(word, (n as u32 + 1).to_ne_bytes());
+	}
+
+	//  A ragged tail is refused, and nothing is converted.
+	let buf = &mut raw[1 .. 11];
+	let before = buf.to_vec();
+	assert_eq!(
+		bytes::convert_bytes_as::<u32>(buf, Endianness::Big),
+		Err(CastError::Length),
+	);
+	assert_eq!(buf, &before[..]);
+}
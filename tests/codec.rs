@@ -0,0 +1,397 @@
+/*! Test the binary codec and its derive
+!*/
+
+#![cfg(feature = "std")]
+
+extern crate endian_trait;
+
+use endian_trait::{
+	Codec,
+	Endianness,
+	codec,
+};
+use std::{
+	io,
+	sync::atomic::{
+		AtomicUsize,
+		Ordering,
+	},
+};
+
+#[repr(u16)]
+#[derive(Clone, Copy, Codec, Debug, Eq, PartialEq)]
+enum Kind {
+	Ping = 1,
+	Pong = 0x0200,
+}
+
+//  This has padding in memory, but none on the wire.
+#[derive(Clone, Copy, Codec, Debug, PartialEq)]
+struct Record {
+	kind: Kind,
+	flag: bool,
+	id: u32,
+	name: [char; 2],
+	ratio: f32,
+}
+
+#[derive(Codec, Debug, PartialEq)]
+struct Pair<A: Codec, B: Codec>(A, B);
+
+#[derive(Codec, Debug, PartialEq)]
+struct Unit;
+
+fn get_record() -> Record {
+	Record {
+		kind: Kind::Pong,
+		flag: true,
+		id: 0x01020304,
+		name: ['h', 'i'],
+		ratio: 1.5,
+	}
+}
+
+#[test]
+fn encode_record() {
+	let mut be = Vec::new();
+	codec::encode_be(&get_record(), &mut be).unwrap();
+	assert_eq!(be, [
+		2, 0,
+		1,
+		1, 2, 3, 4,
+		0, 0, 0, b'h', 0, 0, 0, b'i',
+		0x3F, 0xC0, 0, 0,
+	]);
+
+	let mut le = Vec::new();
+	codec::encode_le(&get_record(), &mut le).unwrap();
+	assert_eq!(le, [
+		0, 2,
+		1,
+		4, 3, 2, 1,
+		b'h', 0, 0, 0, b'i', 0, 0, 0,
+		0, 0, 0xC0, 0x3F,
+	]);
+
+	assert_eq!(codec::decode_be::<Record>(&mut &be[..]).unwrap(), get_record());
+	assert_eq!(codec::decode_le::<Record>(&mut &le[..]).unwrap(), get_record());
+}
+
+#[test]
+fn generic_and_empty() {
+	let pair = Pair(Kind::Ping, Pair(-1i8, Unit));
+	let mut wire = Vec::new();
+	pair.encode(Endianness::Little, &mut wire).unwrap();
+	assert_eq!(wire, [1, 0, 0xFF]);
+
+	let back = Pair::decode(Endianness::Little, &mut &wire[..]).unwrap();
+	assert_eq!(back, pair);
+}
+
+#[test]
+fn invalid_input() {
+	let err = codec::decode_be::<Kind>(&mut &[0u8, 2][..]).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+	let err = codec::decode_be::<bool>(&mut &[2u8][..]).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+	let err = codec::decode_be::<char>(&mut &[0u8, 0xD8, 0, 0][..]).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+	let err = codec::decode_be::<Record>(&mut &[2u8, 0, 1, 1][..]).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+//  Counts how many of its values have been dropped.
+struct Counted;
+
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+impl Drop for Counted {
+	fn drop(&mut self) {
+		DROPPED.fetch_add(1, Ordering::SeqCst);
+	}
+}
+
+impl Codec for Counted {
+	fn encode<W>(&self, order: Endianness, writer: &mut W) -> io::Result<()>
+	where W: io::Write + ?Sized {
+		true.encode(order, writer)
+	}
+
+	fn decode<R>(order: Endianness, reader: &mut R) -> io::Result<Self>
+	where R: io::Read + ?Sized {
+		bool::decode(order, reader).map(|_| Counted)
+	}
+}
+
+#[test]
+fn partial_arrays() {
+	let arr = codec::decode_be::<[u16; 3]>(&mut &[0u8, 1, 0, 2, 0, 3][..]);
+	assert_eq!(arr.unwrap(), [1, 2, 3]);
+
+	//  The elements decoded before the failure are dropped, and no others.
+	let err = codec::decode_be::<[Counted; 4]>(&mut &[1u8, 1, 2][..]);
+	assert_eq!(err.err().unwrap().kind(), io::ErrorKind::InvalidData);
+	assert_eq!(DROPPED.load(Ordering::SeqCst), 2);
+}
+
+//  The start of an IPv4 header, with its sub-byte fields.
+#[derive(Clone, Copy, Codec, Debug, PartialEq)]
+struct Ipv4Start {
+	#[endian(bits = 4)]
+	version: u8,
+	#[endian(bits = 4)]
+	ihl: u8,
+	#[endian(bits = 6)]
+	dscp: u8,
+	#[endian(bits = 2)]
+	ecn: u8,
+	total_length: u16,
+	identification: u16,
+	#[endian(bits = 1)]
+	reserved: bool,
+	#[endian(bits = 1)]
+	dont_fragment: bool,
+	#[endian(bits = 1)]
+	more_fragments: bool,
+	#[endian(bits = 13)]
+	fragment_offset: u16,
+}
+
+#[derive(Clone, Copy, Codec, Debug, PartialEq)]
+#[endian(bit_order = "lsb")]
+struct Register(
+	#[endian(bits = 3)] i8,
+	#[endian(bits = 5)] u8,
+	#[endian(bits = 16)] i32,
+	#[endian(bits = 8)] u8,
+);
+
+#[test]
+fn bit_fields() {
+	let head = Ipv4Start {
+		version: 4,
+		ihl: 5,
+		dscp: 0x2E,
+		ecn: 1,
+		total_length: 0x0054,
+		identification: 0xABCD,
+		reserved: false,
+		dont_fragment: true,
+		more_fragments: false,
+		fragment_offset: 0x0123,
+	};
+	let mut wire = Vec::new();
+	codec::encode_be(&head, &mut wire).unwrap();
+	assert_eq!(wire, [0x45, 0xB9, 0x00, 0x54, 0xAB, 0xCD, 0x41, 0x23]);
+	assert_eq!(codec::decode_be::<Ipv4Start>(&mut &wire[..]).unwrap(), head);
+
+	//  The first field is in the low bits, and signed fields are
+	//  sign-extended.
+	let reg = Register(-2, 17, -300, 0xC3);
+	let mut wire = Vec::new();
+	codec::encode_le(&reg, &mut wire).unwrap();
+	assert_eq!(wire, [0x8E, 0xD4, 0xFE, 0xC3]);
+	assert_eq!(codec::decode_le::<Register>(&mut &wire[..]).unwrap(), reg);
+
+	let err = codec::encode_be(&Ipv4Start { version: 16, .. head }, &mut wire)
+		.unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+	assert!(codec::encode_le(&Register(-5, 0, 0, 0), &mut wire).is_err());
+	assert!(codec::encode_le(&Register(3, 0, 32767, 0), &mut wire).is_ok());
+}
+
+#[test]
+fn addresses() {
+	use std::net::{
+		Ipv4Addr,
+		SocketAddrV4,
+	};
+
+	#[derive(Codec, Debug, PartialEq)]
+	struct Peer {
+		addr: SocketAddrV4,
+		gateway: Ipv4Addr,
+	}
+
+	let peer = Peer {
+		addr: SocketAddrV4::new(Ipv4Addr::new(10, 1, 2, 3), 53),
+		gateway: Ipv4Addr::new(10, 1, 0, 1),
+	};
+	let mut wire = Vec::new();
+	codec::encode_le(&peer, &mut wire).unwrap();
+	assert_eq!(wire, [10, 1, 2, 3, 53, 0, 10, 1, 0, 1]);
+	assert_eq!(codec::decode_le::<Peer>(&mut &wire[..]).unwrap(), peer);
+}
+
+#[test]
+fn durations() {
+	use endian_trait::time::WireDuration;
+	use std::time::Duration;
+
+	#[derive(Codec, Debug, PartialEq)]
+	struct Timeout(u8, #[endian(as = WireDuration)] Duration);
+
+	let timeout = Timeout(7, Duration::new(2, 5));
+	let mut wire = Vec::new();
+	codec::encode_le(&timeout, &mut wire).unwrap();
+	assert_eq!(wire, [7, 2, 0, 0, 0, 0, 0, 0, 0, 5, 0, 0, 0]);
+	assert_eq!(codec::decode_le::<Timeout>(&mut &wire[..]).unwrap(), timeout);
+
+	//  Nanoseconds that overflow into the seconds are invalid.
+	wire[9 ..].copy_from_slice(&1_000_000_000u32.to_le_bytes());
+	let err = codec::decode_le::<Timeout>(&mut &wire[..]).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn padding() {
+	#[derive(Codec, Debug, PartialEq)]
+	struct Header {
+		tag: u8,
+		#[endian(align = 4)]
+		len: u32,
+		#[endian(pad_before = 2, reserved = 3)]
+		flags: u16,
+		#[endian(align = 8)]
+		end: u8,
+	}
+
+	let header = Header { tag: 1, len: 2, flags: 3, end: 4 };
+	let mut wire = Vec::new();
+	codec::encode_be(&header, &mut wire).unwrap();
+	assert_eq!(wire, [
+		1, 0, 0, 0,
+		0, 0, 0, 2,
+		0, 0, 0, 3, 0, 0, 0, 0,
+		4,
+	]);
+	assert_eq!(codec::decode_be::<Header>(&mut &wire[..]).unwrap(), header);
+
+	//  Padding is passed over, but reserved bytes must still be zero.
+	wire[1] = 0xFF;
+	assert_eq!(codec::decode_be::<Header>(&mut &wire[..]).unwrap(), header);
+	wire[13] = 0xFF;
+	let err = codec::decode_be::<Header>(&mut &wire[..]).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn sequences() {
+	#[derive(Codec, Debug, PartialEq)]
+	struct Message {
+		kind: u8,
+		count: u16,
+		#[endian(len = "count")]
+		values: Vec<u32>,
+	}
+
+	#[derive(Codec, Debug, PartialEq)]
+	struct Samples(u8, #[endian(len = "0")] Box<[i16]>);
+
+	let msg = Message { kind: 9, count: 2, values: vec![1, 0x0203] };
+	let mut wire = Vec::new();
+	codec::encode_be(&msg, &mut wire).unwrap();
+	assert_eq!(wire, [9, 0, 2, 0, 0, 0, 1, 0, 0, 2, 3]);
+	assert_eq!(codec::decode_be::<Message>(&mut &wire[..]).unwrap(), msg);
+
+	//  The input runs out before the count does.
+	let err = codec::decode_be::<Message>(&mut &wire[.. 9]).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+	//  The count must match the sequence it describes.
+	let bad = Message { count: 3, ..msg };
+	let err = codec::encode_be(&bad, &mut Vec::new()).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+	let samples = Samples(3, vec![-1, 2, -3].into_boxed_slice());
+	let mut wire = Vec::new();
+	codec::encode_le(&samples, &mut wire).unwrap();
+	assert_eq!(wire, [3, 0xFF, 0xFF, 2, 0, 0xFD, 0xFF]);
+	assert_eq!(codec::decode_le::<Samples>(&mut &wire[..]).unwrap(), samples);
+	assert_eq!(
+		codec::decode_le::<Samples>(&mut &[0][..]).unwrap(),
+		Samples(0, Box::new([])),
+	);
+}
+
+#[test]
+fn checksums() {
+	use endian_trait::checksum;
+
+	fn xor(bytes: &[u8]) -> u8 {
+		bytes.iter().fold(0, |sum, byte| sum ^ byte)
+	}
+
+	#[derive(Codec, Debug, PartialEq)]
+	struct Packet {
+		id: u16,
+		len: u8,
+		#[endian(len = "len")]
+		body: Vec<u8>,
+		#[endian(checksum = custom(xor))]
+		parity: u8,
+		#[endian(checksum = crc32)]
+		crc: u32,
+	}
+
+	//  The checksums are filled in, whatever the fields hold.
+	let packet = Packet { id: 1, len: 2, body: vec![3, 4], parity: 0, crc: 0 };
+	let mut wire = Vec::new();
+	codec::encode_be(&packet, &mut wire).unwrap();
+	assert_eq!(wire[.. 6], [0, 1, 2, 3, 4, 4]);
+	assert_eq!(wire[6 ..], checksum::crc32(&wire[.. 6]).to_be_bytes());
+
+	let back = codec::decode_be::<Packet>(&mut &wire[..]).unwrap();
+	assert_eq!(back, Packet {
+		parity: 4,
+		crc: checksum::crc32(&wire[.. 6]),
+		..packet
+	});
+
+	wire[3] ^= 0x10;
+	let err = codec::decode_be::<Packet>(&mut &wire[..]).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn constants() {
+	use endian_trait::codec::DetectOrder;
+
+	#[derive(Codec, Debug, PartialEq)]
+	struct Capture {
+		#[endian(magic = 0xA1B2C3D4, detect_order)]
+		magic: u32,
+		#[endian(const = [b'p', b'c'])]
+		kind: [u8; 2],
+		snaplen: u16,
+	}
+
+	//  The constants are written whatever the fields hold.
+	let capture = Capture { magic: 0, kind: [0; 2], snaplen: 0x0102 };
+	let mut wire = Vec::new();
+	codec::encode_le(&capture, &mut wire).unwrap();
+	assert_eq!(wire, [0xD4, 0xC3, 0xB2, 0xA1, b'p', b'c', 2, 1]);
+
+	let expected = Capture { magic: 0xA1B2C3D4, kind: *b"pc", snaplen: 0x0102 };
+	assert_eq!(codec::decode_le::<Capture>(&mut &wire[..]).unwrap(), expected);
+	let (back, order) = Capture::decode_detect(&mut &wire[..]).unwrap();
+	assert_eq!((back, order), (expected, Endianness::Little));
+
+	let mut be = Vec::new();
+	codec::encode_be(&capture, &mut be).unwrap();
+	assert_eq!(Capture::decode_detect(&mut &be[..]).unwrap().1, Endianness::Big);
+
+	//  Read in the wrong order, the magic number does not match.
+	let err = codec::decode_be::<Capture>(&mut &wire[..]).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+	wire[5] = b'x';
+	let err = Capture::decode_detect(&mut &wire[..]).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+	wire[0] = 0;
+	let err = Capture::decode_detect(&mut &wire[..]).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
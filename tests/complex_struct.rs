@@ -5,6 +5,9 @@ merely UB to transmute them into byte arrays and access into them like that,
 which is what all the other tests are doing.
 !*/
 
+//  Newer compilers suggest `to_ne_bytes` over these transmutes.
+#![allow(unknown_lints, unnecessary_transmutes)]
+
 extern crate endian_trait;
 
 use endian_trait::Endian;
@@ -0,0 +1,71 @@
+/*! Test `const` conversions, on the primitives and on derived structs
+!*/
+
+extern crate endian_trait;
+
+use endian_trait::{
+	ConstEndian,
+	Endian,
+	const_be,
+	const_le,
+};
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, Endian, PartialEq)]
+#[endian(const_fn)]
+struct Inner(u16, i8);
+
+#[derive(Clone, Copy, Debug, Endian, PartialEq)]
+#[endian(const_fn)]
+struct Header {
+	magic: u32,
+	flags: bool,
+	inner: Inner,
+	ratio: f64,
+	#[endian(as = endian_trait::time::WireDuration)]
+	kept: Duration,
+}
+
+#[derive(Clone, Copy, Debug, Endian, PartialEq)]
+#[endian(const_fn)]
+struct Empty {}
+
+const VALUE: Header = Header {
+	magic: 0xCAFE_BABE,
+	flags: true,
+	inner: Inner(0x0102, -3),
+	ratio: 1.5,
+	kept: Duration::from_millis(1500),
+};
+
+const MAGIC_BE: u32 = const_be::<u32>(0xCAFE_BABE);
+const MAGIC_LE: u32 = const_le::<u32>(0xCAFE_BABE);
+const BE: Header = VALUE.to_be_const();
+const LE: Header = VALUE.to_le_const();
+
+#[test]
+fn primitives() {
+	assert_eq!(MAGIC_BE, 0xCAFE_BABEu32.to_be());
+	assert_eq!(MAGIC_LE, 0xCAFE_BABEu32.to_le());
+	assert_eq!(const_be(-2i128), (-2i128).to_be());
+	assert_eq!(const_le(0x0102u16), 0x0102u16.to_le());
+	assert_eq!(const_be(1.5f32).to_bits(), 1.5f32.to_bits().to_be());
+	assert!(const_be(true));
+}
+
+#[test]
+fn derived() {
+	assert_eq!(BE, VALUE.to_be());
+	assert_eq!(LE, VALUE.to_le());
+	assert_eq!(BE.from_be_const(), VALUE);
+	assert_eq!(LE.from_le_const(), VALUE);
+	assert_eq!(Empty {}.to_be_const(), Empty {});
+}
+
+#[test]
+fn swaps() {
+	//  Single-byte fields have ranges of their own, but kept fields have none.
+	assert_eq!(<Inner as ConstEndian>::SWAPS.len(), 2);
+	assert_eq!(<Header as ConstEndian>::SWAPS.len(), 5);
+	assert!(<Empty as ConstEndian>::SWAPS.is_empty());
+}
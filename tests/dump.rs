@@ -0,0 +1,81 @@
+/*! Test annotated hex dumps
+!*/
+
+extern crate endian_trait;
+
+use endian_trait::{
+	Endian,
+	EndianDump,
+};
+
+#[repr(u16)]
+#[derive(Clone, Copy, Endian, EndianDump)]
+enum Kind {
+	Ping = 1,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Endian, EndianDump)]
+struct Header {
+	kind: Kind,
+	len: u16,
+	id: u32,
+}
+
+//  Dumping does not need `Endian` itself, so the array needs no feature.
+#[repr(C)]
+#[derive(EndianDump)]
+struct Packet {
+	header: Header,
+	flag: bool,
+	body: [u8; 2],
+	letter: char,
+}
+
+#[repr(C)]
+#[derive(Endian, EndianDump)]
+struct Pair<A: Endian, B: Endian>(A, B);
+
+/// Squashes the alignment spaces out of a dump, so that tests can spell it
+/// compactly.
+fn squash(dump: impl ToString) -> Vec<String> {
+	dump.to_string()
+		.lines()
+		.skip(1)
+		.map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+		.collect()
+}
+
+#[test]
+fn nested() {
+	let packet = Packet {
+		header: Header { kind: Kind::Ping, len: 0x0203, id: 0x04050607 },
+		flag: true,
+		body: [8, 9],
+		letter: 'A',
+	};
+	assert_eq!(squash(packet.dump_be()), [
+		"0000 00 01 header.kind: Kind",
+		"0002 02 03 header.len: u16",
+		"0004 04 05 06 07 header.id: u32",
+		"0008 01 flag: bool",
+		"0009 08 body[0]: u8",
+		"000A 09 body[1]: u8",
+		"000C 00 00 00 41 letter: char",
+	]);
+	assert_eq!(squash(packet.dump_le())[2], "0004 07 06 05 04 header.id: u32");
+}
+
+#[test]
+fn generic_and_primitive() {
+	let pair = Pair(1u8, Pair(-2i16, 3u32));
+	assert_eq!(squash(pair.dump_le()), [
+		"0000 01 0: u8",
+		"0004 FE FF 1.0: i16",
+		"0008 03 00 00 00 1.1: u32",
+	].iter().map(|line| line.to_string()).collect::<Vec<_>>());
+
+	assert_eq!(format!("{:?}", 7u16.dump_be()).lines().nth(1), Some(
+		format!("0000  00 07{:42}  u16", "").as_str(),
+	));
+}
@@ -1,19 +1,22 @@
 /*! Test Endian derivation on a generic struct
 
 This test ensures that a generic struct can have Endian derived on it, so long
-as it is composed of components which are all themselves Endian. This means that
-all generic type parameters must be bound by Endian:
+as it is composed of components which are all themselves Endian. The derive
+requires each field's type to be Endian in the impl's `where` clause, so generic
+type parameters do not need to be bound by Endian themselves:
 
 ```rust
 #[derive(Endian)]
-struct ExampleGeneric<A> where A: Endian {
+struct ExampleGeneric<A> {
 	a: A,
 }
 ```
 
-So long as this bound is satisfied, Endian can be derived on any generic struct.
+is Endian whenever `A` is.
 !*/
 
+#![allow(clippy::approx_constant, clippy::tabs_in_doc_comments)]
+
 extern crate endian_trait;
 
 use endian_trait::Endian;
@@ -50,3 +53,14 @@ fn generic_struct() {
 
 	assert_eq!(gb.from_be(), gl.from_le());
 }
+
+#[derive(Clone, Copy, Debug, Endian, PartialEq)]
+struct Unbound<T>(T, u16);
+
+#[test]
+fn unbound_params() {
+	let val = Unbound(0x0102_0304u32, 0x0506);
+	assert_eq!(val.to_be().from_be(), val);
+	assert_eq!(val.to_le().from_le(), val);
+	assert_eq!(val.to_be().0, 0x0102_0304u32.to_be());
+}
@@ -1,26 +1,22 @@
 /*! Invalid Code Generation
 
 The macro should successfully emit Rust code that has the correct shape, but
-which will then fail to compile.
+which will then fail to compile. Each file in `tests/ui` must fail to compile
+with exactly the errors in its `.stderr` file, which point at the fields or
+options that are at fault rather than at the derive.
 
-When the rest of this code is uncommented, compilation must fail. When it is
-disabled, compilation must succeed.
+The compiler's messages change between versions, so the expected output is kept
+up to date with the toolchain pinned in `rust-toolchain`, and this test is
+ignored on any other compiler. Run it with `TRYBUILD=overwrite` to rewrite the
+`.stderr` files after a change.
 !*/
 
-/*
-#![compile_fail]
-
-extern crate endian_trait;
-
-use endian_trait::Endian;
-
-struct Foo {
-	a: i32
-}
-
-#[derive(Endian)]
-struct Bar {
-	a: Foo,
-	b: i32
+//  This must come before `#[test]`, which reads `#[ignore]` as it expands.
+#[rustversion::attr(
+	not(stable(1.83.0)),
+	ignore = "compiler messages are kept for the pinned toolchain",
+)]
+#[test]
+fn compile_fail() {
+	trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
 }
-*/
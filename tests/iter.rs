@@ -0,0 +1,58 @@
+/*! Test converting iterators of `Endian` values
+!*/
+
+extern crate endian_trait;
+
+use endian_trait::{
+	Endian,
+	EndianIterExt,
+	Endianness,
+};
+use endian_trait::iter::convert_all;
+
+#[derive(Clone, Copy, Debug, Endian, PartialEq)]
+struct Sample {
+	channel: u8,
+	value: i32,
+}
+
+fn samples() -> Vec<Sample> {
+	(1 ..= 3).map(|n| Sample { channel: n, value: -(n as i32) }).collect()
+}
+
+#[test]
+fn adapters() {
+	let wire = samples().into_iter().to_be().collect::<Vec<_>>();
+	assert_eq!(wire[0].value.to_ne_bytes(), [0xFF, 0xFF, 0xFF, 0xFF]);
+	assert_eq!(wire[2].value.to_ne_bytes(), [0xFF, 0xFF, 0xFF, 0xFD]);
+
+	let back = wire.iter().copied().from_be();
+	assert_eq!(back.len(), 3);
+	assert_eq!(back.rev().collect::<Vec<_>>(), {
+		let mut s = samples();
+		s.reverse();
+		s
+	});
+
+	for order in [Endianness::Big, Endianness::Little] {
+		let out = samples().into_iter()
+			.to_order(order)
+			.from_order(order)
+			.collect::<Vec<_>>();
+		assert_eq!(out, samples());
+	}
+
+	let le = [0x0102u16].iter().copied().to_le().next().unwrap();
+	assert_eq!(le.to_ne_bytes(), [2, 1]);
+}
+
+#[test]
+fn in_place() {
+	let mut buf = samples();
+	convert_all(buf.iter_mut(), Endian::to_le);
+	assert_eq!(buf[1].value.to_ne_bytes(), [0xFE, 0xFF, 0xFF, 0xFF]);
+
+	let order = Endianness::Little;
+	convert_all(&mut buf, |s| order.convert_from(s));
+	assert_eq!(buf, samples());
+}
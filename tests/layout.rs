@@ -0,0 +1,76 @@
+/*! Test the compile-time layout assertions emitted by the derive
+
+These structs only compile because their layouts satisfy the assertions placed
+on them. A struct whose layout does not, such as
+
+```rust,ignore
+#[repr(C)]
+#[derive(Endian)]
+#[endian(no_padding)]
+struct Padded {
+    a: u8,
+    b: u32,
+}
+```
+
+fails to compile with an error naming the type, as `tests/ui/layout.rs` checks.
+!*/
+
+extern crate endian_trait;
+
+use endian_trait::Endian;
+use std::mem::size_of;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Endian, Eq, PartialEq)]
+#[endian(no_padding, size = 16)]
+struct Header {
+	magic: u32,
+	version: u16,
+	flags: u16,
+	length: u64,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Endian)]
+#[endian(no_padding)]
+#[endian(size = 7)]
+struct Packed(u8, u32, u16);
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Endian, Eq, PartialEq)]
+#[endian(no_padding)]
+struct Nested {
+	header: Header,
+	checksum: u64,
+}
+
+#[derive(Clone, Copy, Endian)]
+#[endian(no_padding, size = 0)]
+struct Empty;
+
+#[test]
+fn unpadded_sizes() {
+	assert_eq!(size_of::<Header>(), 16);
+	assert_eq!(size_of::<Packed>(), 7);
+	assert_eq!(size_of::<Nested>(), 24);
+	assert_eq!(size_of::<Empty>(), 0);
+}
+
+#[test]
+fn still_converts() {
+	let header = Header {
+		magic: 0xA1B2C3D4,
+		version: 2,
+		flags: 0x8001,
+		length: 0x0102030405060708,
+	};
+	let nested = Nested {
+		header,
+		checksum: 0xdeadbeef,
+	};
+	let be = nested.to_be();
+	assert_eq!(be.header.magic.to_ne_bytes(), [0xA1, 0xB2, 0xC3, 0xD4]);
+	assert_eq!(be.checksum.to_ne_bytes(), [0, 0, 0, 0, 0xde, 0xad, 0xbe, 0xef]);
+	assert_eq!(be.from_be(), nested);
+}
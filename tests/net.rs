@@ -0,0 +1,71 @@
+/*! Test `Endian` on the `core::net` address types
+!*/
+
+extern crate endian_trait;
+
+use endian_trait::{
+	Endian,
+	Endianness,
+	net,
+};
+use std::net::{
+	Ipv4Addr,
+	Ipv6Addr,
+	SocketAddr,
+	SocketAddrV4,
+	SocketAddrV6,
+};
+
+//  A struct holding addresses can derive `Endian`.
+#[derive(Clone, Copy, Debug, Endian, PartialEq)]
+struct Route {
+	dest: Ipv4Addr,
+	peer: SocketAddrV4,
+	metric: u16,
+}
+
+#[test]
+fn network_order() {
+	let route = Route {
+		dest: Ipv4Addr::new(10, 0, 0, 1),
+		peer: SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 2), 0x1F90),
+		metric: 0x0102,
+	};
+
+	let be = route.to_be();
+	assert_eq!(be.dest, route.dest);
+	assert_eq!(be.peer.ip(), route.peer.ip());
+	assert_eq!(be.peer.port().to_ne_bytes(), [0x1F, 0x90]);
+	assert_eq!(be.metric.to_ne_bytes(), [1, 2]);
+	assert_eq!(be.from_be(), route);
+	assert_eq!(route.to_le().from_le(), route);
+}
+
+#[test]
+fn v6_fields() {
+	let addr = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 443, 0x000A_BCDE, 3);
+	let be = addr.to_be();
+	assert_eq!(be.ip(), &Ipv6Addr::LOCALHOST);
+	assert_eq!(be.port().to_ne_bytes(), [1, 0xBB]);
+	assert_eq!(be.flowinfo().to_ne_bytes(), [0, 0x0A, 0xBC, 0xDE]);
+	assert_eq!(be.scope_id().to_ne_bytes(), [0, 0, 0, 3]);
+
+	let any = SocketAddr::V6(addr);
+	assert_eq!(any.to_be(), SocketAddr::V6(be));
+	assert_eq!(any.to_le().from_le(), any);
+}
+
+#[test]
+fn wire_layout() {
+	let v4 = SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080);
+	let be = net::v4_to_bytes(v4, Endianness::Big);
+	assert_eq!(be, [127, 0, 0, 1, 0x1F, 0x90]);
+	assert_eq!(net::v4_to_bytes(v4, Endianness::Little)[4 ..], [0x90, 0x1F]);
+	assert_eq!(net::v4_from_bytes(be, Endianness::Big), v4);
+
+	let v6 = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 443, 7, 2);
+	let le = net::v6_to_bytes(v6, Endianness::Little);
+	assert_eq!(le[15], 1);
+	assert_eq!(le[16 ..], [0xBB, 1, 7, 0, 0, 0, 2, 0, 0, 0]);
+	assert_eq!(net::v6_from_bytes(le, Endianness::Little), v6);
+}
@@ -0,0 +1,73 @@
+/*! Test parsing `Endian` types with `nom`
+!*/
+
+#![cfg(feature = "nom")]
+
+extern crate endian_trait;
+extern crate nom;
+
+use endian_trait::Endian;
+use endian_trait::nom::{
+	be,
+	le,
+};
+use nom::{
+	Err,
+	IResult,
+	Parser,
+	error::{
+		Error,
+		ErrorKind,
+	},
+	multi::count,
+	number::complete::le_u8,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Endian, Eq, PartialEq)]
+#[endian(from_bytes)]
+struct Record {
+	id: u32,
+	delta: i16,
+	flags: u16,
+}
+
+const BE: [u8; 8] = [1, 2, 3, 4, 0xFF, 0xFE, 0x80, 0];
+const LE: [u8; 8] = [4, 3, 2, 1, 0xFE, 0xFF, 0, 0x80];
+
+fn get_record() -> Record {
+	Record {
+		id: 0x01020304,
+		delta: -2,
+		flags: 0x8000,
+	}
+}
+
+#[test]
+fn parse_struct() {
+	let rec = get_record();
+	let out: IResult<_, _, Error<_>> = be::<Record>().parse(&BE[..]);
+	assert_eq!(out, Ok((&[][..], rec)));
+	let out: IResult<_, _, Error<_>> = le::<Record>().parse(&LE[..]);
+	assert_eq!(out, Ok((&[][..], rec)));
+}
+
+#[test]
+fn combinators() {
+	fn table(input: &[u8]) -> IResult<&[u8], Vec<Record>> {
+		let (input, n) = le_u8(input)?;
+		count(le::<Record>(), n as usize)(input)
+	}
+
+	let mut input = vec![2];
+	input.extend_from_slice(&LE);
+	input.extend_from_slice(&LE);
+	input.push(0xAA);
+	assert_eq!(table(&input), Ok((&[0xAA][..], vec![get_record(); 2])));
+}
+
+#[test]
+fn short_input() {
+	let out: IResult<_, u32, Error<_>> = be::<u32>().parse(&BE[.. 3]);
+	assert_eq!(out, Err(Err::Error(Error::new(&BE[.. 3], ErrorKind::Eof))));
+}
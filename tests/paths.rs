@@ -0,0 +1,121 @@
+/*! Test the paths that derived code uses to reach this crate
+
+The derives name everything through `::endian_trait`, so they work when the
+`Endian` trait is imported under another name, or not at all, and through the
+path given by `#[endian(crate = "…")]` when this crate is re-exported.
+!*/
+
+extern crate endian_trait;
+
+use endian_trait::Endian as Swap;
+
+/// A crate that re-exports `endian_trait` to its own users.
+mod facade {
+	pub extern crate endian_trait as inner;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Swap)]
+struct Aliased {
+	a: u16,
+	b: i32,
+}
+
+mod unimported {
+	#[derive(Clone, Copy, Debug, PartialEq, endian_trait::Endian)]
+	#[endian(wire)]
+	pub struct Record(pub u32, pub bool);
+
+	#[derive(Clone, Copy, Debug, PartialEq, endian_trait::Endian)]
+	#[repr(u16)]
+	pub enum Kind {
+		A = 1,
+		B = 0x0200,
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Swap)]
+#[derive(endian_trait::EndianDump)]
+#[endian(crate = "crate::facade::inner", wire, no_padding)]
+#[repr(C)]
+struct Faced {
+	#[endian(bits = 4)]
+	hi: u8,
+	#[endian(bits = 4)]
+	lo: u8,
+	tag: u8,
+	flags: u8,
+	len: u16,
+}
+
+#[test]
+fn aliased() {
+	let val = Aliased { a: 0x0102, b: -2 };
+	assert_eq!(Swap::to_be(val).a, 0x0102u16.to_be());
+	assert_eq!(Swap::from_le(Swap::to_le(val)), val);
+}
+
+#[test]
+fn unimported() {
+	use endian_trait::wire::Wire;
+	use unimported::{
+		Kind,
+		Record,
+	};
+
+	let rec = Record(0x01020304, true);
+	let mut buf = [0; 5];
+	rec.write_be_into(&mut buf);
+	assert_eq!(buf, [1, 2, 3, 4, 1]);
+	assert_eq!(Record::read_be_from(&buf), rec);
+	for kind in [Kind::A, Kind::B] {
+		assert_eq!(Swap::from_be(Swap::to_be(kind)), kind);
+	}
+}
+
+#[test]
+fn crate_path() {
+	use endian_trait::{
+		EndianDump,
+		wire::Wire,
+	};
+
+	let val = Faced { hi: 0xA, lo: 0x5, tag: 7, flags: 0, len: 0x0102 };
+	assert_eq!(Swap::from_be(Swap::to_be(val)), val);
+	assert_eq!(Faced::WIRE_SIZE, 5);
+	let mut buf = [0; 5];
+	val.write_be_into(&mut buf);
+	assert_eq!(buf, [0xA5, 7, 0, 1, 2]);
+	assert!(format!("{:?}", val.dump_be()).contains("len: u16"));
+}
+
+#[cfg(feature = "std")]
+mod codec {
+	use endian_trait::Codec;
+	use endian_trait::codec::{
+		decode_le,
+		encode_le,
+	};
+
+	#[derive(Codec, Debug, PartialEq)]
+	#[endian(crate = "crate::facade::inner")]
+	struct Framed {
+		#[endian(magic = 0xCAFE)]
+		magic: u16,
+		len: u8,
+		#[endian(len = "len")]
+		body: Vec<u8>,
+		#[endian(checksum = crc32)]
+		sum: u32,
+	}
+
+	#[test]
+	fn crate_path() {
+		let val = Framed { magic: 0xCAFE, len: 2, body: vec![9, 8], sum: 0 };
+		let mut buf = Vec::new();
+		encode_le(&val, &mut buf).unwrap();
+		assert_eq!(&buf[.. 5], &[0xFE, 0xCA, 2, 9, 8]);
+		let back = decode_le::<Framed>(&mut &buf[..]).unwrap();
+		assert_eq!(back.body, val.body);
+		assert_eq!(back.sum, endian_trait::checksum::crc32(&buf[.. 5]));
+	}
+}
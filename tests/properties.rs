@@ -0,0 +1,144 @@
+/*! Property tests of the `Endian` implementations on the primitives
+!*/
+
+#![cfg(feature = "testing")]
+
+extern crate endian_trait;
+
+use endian_trait::Endian;
+use endian_trait::testing::{
+	assert_endian_roundtrip,
+	assert_slice_roundtrip,
+	check_roundtrip,
+	fuzz_endian_roundtrip,
+	fuzz_slice_roundtrip,
+};
+use proptest::prelude::*;
+
+/// Checks every law, and the exact bytes produced, for each integer type.
+macro_rules! ints {
+	( $( $t:ident ),* ) => { $(
+		mod $t {
+			use super::*;
+
+			proptest! {
+				#[test]
+				fn bytes(n: $t) {
+					prop_assert_eq!(n.to_be().to_ne_bytes(), n.to_be_bytes());
+					prop_assert_eq!(n.to_le().to_ne_bytes(), n.to_le_bytes());
+					let be = $t::from_ne_bytes(n.to_be_bytes());
+					let le = $t::from_ne_bytes(n.to_le_bytes());
+					prop_assert_eq!(be.from_be(), n);
+					prop_assert_eq!(le.from_le(), n);
+				}
+			}
+
+			#[test]
+			fn laws() {
+				assert_endian_roundtrip::<$t>();
+				assert_slice_roundtrip::<$t>();
+				for n in [$t::MIN, $t::MAX, 0] {
+					check_roundtrip(&n);
+				}
+			}
+		}
+	)* };
+}
+
+ints!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize);
+
+/// Checks every law, and the exact bits produced, for each float type. Bits are
+/// compared rather than values, so that NaNs are held to their payloads.
+macro_rules! floats {
+	( $( $t:ident : $bits:ident ),* ) => { $(
+		mod $t {
+			use super::*;
+
+			proptest! {
+				#[test]
+				fn bits(bits: $bits) {
+					let f = $t::from_bits(bits);
+					prop_assert_eq!(f.to_be().to_bits(), bits.to_be());
+					prop_assert_eq!(f.to_le().to_bits(), bits.to_le());
+					prop_assert_eq!(f.to_be().from_be().to_bits(), bits);
+				}
+			}
+
+			#[test]
+			fn laws() {
+				assert_endian_roundtrip::<$t>();
+				assert_slice_roundtrip::<$t>();
+				let quiet = $t::NAN.to_bits();
+				let payloads = [quiet, quiet | 1, !0, (quiet >> 1) | 1];
+				for bits in payloads {
+					check_roundtrip(&$t::from_bits(bits));
+				}
+				let edges = [
+					$t::INFINITY,
+					$t::NEG_INFINITY,
+					$t::MIN_POSITIVE,
+					-0.0,
+				];
+				for f in edges {
+					check_roundtrip(&f);
+				}
+			}
+		}
+	)* };
+}
+
+floats!(f32: u32, f64: u64);
+
+#[test]
+fn bool() {
+	assert_endian_roundtrip::<bool>();
+	assert_slice_roundtrip::<bool>();
+}
+
+//  Most `char`s have no valid converted form, so they are checked through their
+//  `u32` bits, and only converted when those bits are a `char` too.
+proptest! {
+	#[test]
+	fn char_bits(c: char) {
+		let bits = u32::from(c);
+		if let Some(be) = char::from_u32(bits.to_be()) {
+			prop_assert_eq!(c.to_be(), be);
+			prop_assert_eq!(be.from_be(), c);
+		}
+		if let Some(le) = char::from_u32(bits.to_le()) {
+			prop_assert_eq!(c.to_le(), le);
+			prop_assert_eq!(le.from_le(), c);
+		}
+	}
+}
+
+#[test]
+fn char_swappable() {
+	for c in ['\0', '\u{100}', '\u{1000}', '\u{10_0000}'] {
+		assert_eq!(u32::from(c.to_be()), u32::from(c).to_be());
+		assert_eq!(u32::from(c.to_le()), u32::from(c).to_le());
+		assert_eq!(c.to_be().from_be(), c);
+		assert_eq!(c.to_le().from_le(), c);
+	}
+}
+
+#[test]
+#[cfg(feature = "arrays")]
+fn arrays() {
+	assert_endian_roundtrip::<[u16; 0]>();
+	assert_endian_roundtrip::<[u32; 3]>();
+	assert_endian_roundtrip::<[f64; 4]>();
+	assert_endian_roundtrip::<[bool; 32]>();
+	assert_endian_roundtrip::<[[i16; 2]; 2]>();
+}
+
+#[test]
+fn fuzz_entry_points() {
+	let inputs: [&[u8]; 3] = [&[], &[0xFF; 7], &[0x7F, 0xF8, 0, 1, 0x10, 0xFF]];
+	for data in inputs {
+		let _ = fuzz_endian_roundtrip::<u64>(data);
+		let _ = fuzz_endian_roundtrip::<f32>(data);
+		fuzz_slice_roundtrip::<u16>(data).unwrap();
+		fuzz_slice_roundtrip::<f64>(data).unwrap();
+	}
+}
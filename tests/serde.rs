@@ -0,0 +1,101 @@
+/*! Test the fixed-order byte string adapters for `serde`
+!*/
+
+#![cfg(feature = "serde")]
+
+extern crate endian_trait;
+extern crate serde;
+extern crate serde_test;
+
+use endian_trait::Endian;
+use serde::{
+	Deserialize,
+	Serialize,
+};
+use serde_test::{
+	Token,
+	assert_de_tokens,
+	assert_de_tokens_error,
+	assert_tokens,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Endian, Eq, PartialEq)]
+#[endian(from_bytes)]
+struct Id {
+	high: u32,
+	low: u32,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct Block {
+	#[serde(with = "endian_trait::serde::be")]
+	id: Id,
+	#[serde(with = "endian_trait::serde::le")]
+	checksum: u32,
+	height: u32,
+}
+
+fn get_block() -> Block {
+	Block {
+		id: Id {
+			high: 0x01020304,
+			low: 0x05060708,
+		},
+		checksum: 0xdeadbeef,
+		height: 7,
+	}
+}
+
+fn head() -> [Token; 2] {
+	[
+		Token::Struct { name: "Block", len: 3 },
+		Token::Str("id"),
+	]
+}
+
+fn tail() -> [Token; 5] {
+	[
+		Token::Str("checksum"),
+		Token::Bytes(&[0xef, 0xbe, 0xad, 0xde]),
+		Token::Str("height"),
+		Token::U32(7),
+		Token::StructEnd,
+	]
+}
+
+#[test]
+fn byte_strings() {
+	let mut tokens = head().to_vec();
+	tokens.push(Token::Bytes(&[1, 2, 3, 4, 5, 6, 7, 8]));
+	tokens.extend_from_slice(&tail());
+	assert_tokens(&get_block(), &tokens);
+}
+
+#[test]
+fn byte_sequences() {
+	let mut tokens = head().to_vec();
+	tokens.push(Token::Seq { len: Some(8) });
+	tokens.extend((1 ..= 8).map(Token::U8));
+	tokens.push(Token::SeqEnd);
+	tokens.extend_from_slice(&tail());
+	assert_de_tokens(&get_block(), &tokens);
+}
+
+#[test]
+fn wrong_width() {
+	let mut tokens = head().to_vec();
+	tokens.push(Token::Bytes(&[1, 2, 3, 4]));
+	assert_de_tokens_error::<Block>(
+		&tokens,
+		"invalid length 4, expected 8 bytes",
+	);
+
+	let mut tokens = head().to_vec();
+	tokens.push(Token::Seq { len: Some(9) });
+	tokens.extend((1 ..= 9).map(Token::U8));
+	assert_de_tokens_error::<Block>(
+		&tokens,
+		"invalid length 9, expected 8 bytes",
+	);
+}
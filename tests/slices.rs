@@ -1,3 +1,6 @@
+//  Newer compilers suggest `to_ne_bytes` over these transmutes.
+#![allow(unknown_lints, unnecessary_transmutes)]
+
 extern crate endian_trait;
 
 use endian_trait::Endian;
@@ -46,3 +49,39 @@ fn slices() {
 
 	assert_eq!(from_be, from_le);
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn owned() {
+	let src = vec![0x0102u16, 0x0304];
+
+	let be = src.clone().to_be();
+	assert_eq!([1, 2], be[0].to_ne_bytes());
+	assert_eq!([3, 4], be[1].to_ne_bytes());
+	assert_eq!(be.from_be(), src);
+
+	let le = src.clone().into_boxed_slice().to_le();
+	assert_eq!([4, 3], le[1].to_ne_bytes());
+	assert_eq!(le.from_le().into_vec(), src);
+}
+
+//  A conversion that panics unwinds out of the sequence, rather than aborting.
+#[cfg(feature = "std")]
+#[test]
+fn owned_panics() {
+	struct Fails;
+
+	impl Endian for Fails {
+		fn from_be(self) -> Self { panic!("cannot convert") }
+		fn from_le(self) -> Self { panic!("cannot convert") }
+		fn to_be(self) -> Self { panic!("cannot convert") }
+		fn to_le(self) -> Self { panic!("cannot convert") }
+	}
+
+	let res = std::panic::catch_unwind(|| vec![Fails, Fails].to_be());
+	assert!(res.is_err());
+	let res = std::panic::catch_unwind(|| {
+		vec![Fails].into_boxed_slice().from_le()
+	});
+	assert!(res.is_err());
+}
@@ -0,0 +1,48 @@
+/*! Test the `Duration` wire layout, and the `as` field option
+!*/
+
+extern crate endian_trait;
+
+use endian_trait::Endian;
+use endian_trait::time::{
+	DurationError,
+	WireDuration,
+};
+use std::convert::TryFrom;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, Endian, PartialEq)]
+struct Timeout {
+	id: u32,
+	#[endian(as = WireDuration)]
+	after: Duration,
+}
+
+#[test]
+fn convert() {
+	let dur = Duration::new(0x0102, 0x0304);
+	let wire = WireDuration::from(dur);
+	assert_eq!(wire, WireDuration { secs: 0x0102, nanos: 0x0304 });
+	assert_eq!(Duration::try_from(wire), Ok(dur));
+
+	let be = wire.to_be();
+	assert_eq!(be.secs.to_ne_bytes(), [0, 0, 0, 0, 0, 0, 1, 2]);
+	assert_eq!(be.nanos.to_ne_bytes(), [0, 0, 3, 4]);
+	assert_eq!(be.from_be(), wire);
+
+	let bad = WireDuration { secs: 1, nanos: 1_000_000_000 };
+	assert_eq!(Duration::try_from(bad), Err(DurationError));
+}
+
+//  `Endian` leaves the `as` field unchanged, and converts the rest.
+#[test]
+fn derive_endian() {
+	let t = Timeout {
+		id: 0x01020304,
+		after: Duration::from_secs(5),
+	};
+	let be = t.to_be();
+	assert_eq!(be.id.to_ne_bytes(), [1, 2, 3, 4]);
+	assert_eq!(be.after, t.after);
+	assert_eq!(be.from_be(), t);
+}
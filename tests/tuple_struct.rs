@@ -13,6 +13,7 @@ use std::mem::{
 //  This is necessary because my test process is transmuting to a byte array and
 //  I don't want the compiler meddling with what I think the tuple looks like in
 //  memory. Plus, testing Endian on a packed repr is probably a good idea.
+#[allow(clippy::repr_packed_without_abi)]
 #[repr(packed)]
 #[derive(Clone, Copy, Debug, Endian, Eq, PartialEq)]
 struct Tuple(u64, i32, u16, i8);
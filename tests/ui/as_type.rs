@@ -0,0 +1,22 @@
+//  `#[derive(Endian)]` cannot convert a field through its `as` type, so it only
+//  accepts `as` on `Duration` fields, which it leaves as they are. A type of its
+//  own that is named `Duration` is not one of those.
+
+use endian_trait::Endian;
+
+#[derive(Clone, Copy, Endian)]
+struct Widened {
+	#[endian(as = u32)]
+	x: u16,
+}
+
+#[derive(Clone, Copy)]
+struct Duration(u64);
+
+#[derive(Clone, Copy, Endian)]
+struct Timeout {
+	#[endian(as = endian_trait::time::WireDuration)]
+	after: Duration,
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+//  Bit fields cannot be wider than their types, and `#[derive(Endian)]` only
+//  accepts them in `#[endian(wire)]` structs.
+
+use endian_trait::{
+	Codec,
+	Endian,
+};
+
+#[derive(Codec)]
+struct Wide {
+	#[endian(bits = 9)]
+	a: u8,
+	#[endian(bits = 7)]
+	b: u8,
+}
+
+#[derive(Clone, Copy, Endian)]
+struct Unpacked {
+	#[endian(bits = 4)]
+	a: u8,
+	#[endian(bits = 4)]
+	b: u8,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+//  `#[endian(const_fn)]` requires every converted field to be `ConstEndian`,
+//  and reports the ones that are not at the field.
+
+use endian_trait::Endian;
+
+#[derive(Clone, Copy, Endian)]
+#[endian(const_fn)]
+struct Letter {
+	code: u16,
+	letter: char,
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+//  `detect_order` reads the magic number before anything else, so it cannot be
+//  combined with padding or reserved bytes on the same field.
+
+use endian_trait::Codec;
+
+#[derive(Codec)]
+struct Aligned {
+	#[endian(magic = 0xA1B2C3D4, detect_order, align = 4)]
+	magic: u32,
+	version: u16,
+}
+
+#[derive(Codec)]
+struct Reserved {
+	#[endian(magic = 0xA1B2C3D4, detect_order, reserved = 2)]
+	magic: u32,
+	version: u16,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+//  A struct whose layout does not match its `no_padding` or `size` option fails
+//  to compile, naming the type.
+
+use endian_trait::Endian;
+
+#[repr(C)]
+#[derive(Endian)]
+#[endian(no_padding)]
+struct Padded {
+	a: u8,
+	b: u32,
+}
+
+#[repr(C)]
+#[derive(Endian)]
+#[endian(size = 6)]
+struct Wide {
+	a: u16,
+	b: u32,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+//  A field whose type is not `Endian` is reported at that field, by type.
+
+use endian_trait::Endian;
+
+struct Foo {
+	a: i32,
+}
+
+#[derive(Endian)]
+struct Bar {
+	a: Foo,
+	b: i32,
+}
+
+#[derive(Endian)]
+struct Baz(u16, Foo, bool);
+
+fn main() {}
@@ -0,0 +1,16 @@
+//  A generic struct is `Endian` only when its fields are, so the error is
+//  reported where it is used with a type that is not.
+
+use endian_trait::Endian;
+
+struct Foo;
+
+#[derive(Endian)]
+struct Wrapper<T> {
+	inner: T,
+	tag: u8,
+}
+
+fn main() {
+	let _ = Wrapper { inner: Foo, tag: 0 }.to_be();
+}
@@ -0,0 +1,60 @@
+/*! Test values stored without alignment
+!*/
+
+extern crate endian_trait;
+
+use endian_trait::{
+	Endian,
+	Unaligned,
+};
+use endian_trait::bytes::{
+	AsBytes,
+	FromBytes,
+};
+use std::mem;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Endian, PartialEq)]
+#[endian(from_bytes, size = 7)]
+struct Header {
+	kind: u8,
+	length: Unaligned<u32>,
+	checksum: Unaligned<u16>,
+}
+
+#[test]
+fn layout() {
+	assert_eq!(mem::align_of::<Unaligned<u64>>(), 1);
+	assert_eq!(mem::size_of::<Unaligned<u64>>(), 8);
+	assert_eq!(mem::align_of::<Header>(), 1);
+}
+
+#[test]
+fn get_set() {
+	let mut val = Unaligned::new(0u32);
+	val.set_be(0x01020304);
+	assert_eq!(val.as_bytes(), &[1, 2, 3, 4]);
+	assert_eq!(val.get_be(), 0x01020304);
+
+	val.set_le(0x01020304);
+	assert_eq!(val.as_bytes(), &[4, 3, 2, 1]);
+	assert_eq!(val.get_le(), 0x01020304);
+
+	let val = Unaligned::new(0x01020304u32);
+	assert_eq!(val.to_be().get().to_ne_bytes(), [1, 2, 3, 4]);
+	assert_eq!(val.to_le().get().to_ne_bytes(), [4, 3, 2, 1]);
+}
+
+//  A header can be viewed in place at any offset of a buffer.
+#[test]
+fn view_at_offset() {
+	let raw = [0xFF, 2, 0, 0, 0, 9, 0xAB, 0xCD];
+	let header = Header::ref_from(&raw[1 ..]).unwrap();
+	assert_eq!(header.length.get_be(), 9);
+	assert_eq!(header.checksum.get_be(), 0xABCD);
+
+	let local = header.from_be();
+	assert_eq!(local.kind, 2);
+	assert_eq!(local.length.get(), 9);
+	assert_eq!(local.to_be(), *header);
+}
@@ -0,0 +1,224 @@
+/*! Test derived wire layouts, which are independent of Rust's layout
+!*/
+
+extern crate endian_trait;
+
+use endian_trait::{
+	Endian,
+	Unaligned,
+	Wire,
+};
+use endian_trait::time::WireDuration;
+use std::mem;
+use std::time::Duration;
+
+#[repr(u16)]
+#[derive(Clone, Copy, Debug, Endian, Eq, PartialEq)]
+#[endian(wire)]
+enum Kind {
+	Ping = 1,
+	Pong = 0x0200,
+}
+
+//  This has padding in memory, but none on the wire.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Endian, PartialEq)]
+#[endian(wire)]
+struct Record {
+	kind: Kind,
+	flag: bool,
+	id: u32,
+	letter: char,
+	ratio: f32,
+}
+
+#[derive(Debug, Endian, PartialEq)]
+#[endian(wire)]
+struct Pair<A: Endian, B: Endian>(A, B);
+
+#[derive(Clone, Copy, Debug, Endian, PartialEq)]
+#[endian(wire, bit_order = "lsb")]
+struct Flags {
+	#[endian(bits = 3)]
+	level: u8,
+	#[endian(bits = 5)]
+	code: i8,
+	#[endian(as = WireDuration)]
+	delay: Duration,
+	pad: Unaligned<u16>,
+}
+
+fn get_record() -> Record {
+	Record {
+		kind: Kind::Pong,
+		flag: true,
+		id: 0x01020304,
+		letter: 'h',
+		ratio: 1.5,
+	}
+}
+
+#[test]
+fn packed_record() {
+	assert_eq!(mem::size_of::<Record>(), 16);
+	assert_eq!(Record::WIRE_SIZE, 15);
+
+	let mut be = [0; Record::WIRE_SIZE];
+	get_record().write_be_into(&mut be);
+	assert_eq!(be, [
+		2, 0,
+		1,
+		1, 2, 3, 4,
+		0, 0, 0, b'h',
+		0x3F, 0xC0, 0, 0,
+	]);
+	assert_eq!(Record::read_be_from(&be), get_record());
+
+	//  Longer buffers are fine; only the front is used.
+	let mut le = [0xAA; 16];
+	get_record().write_le_into(&mut le);
+	assert_eq!(le[.. 7], [0, 2, 1, 4, 3, 2, 1]);
+	assert_eq!(le[15], 0xAA);
+	assert_eq!(Record::read_le_from(&le), get_record());
+}
+
+#[test]
+fn generic_and_attributes() {
+	type P = Pair<u8, Pair<i16, u16>>;
+	assert_eq!(P::WIRE_SIZE, 5);
+	let pair: P = Pair(1, Pair(-2, 0x0403));
+	let mut buf = [0; 5];
+	pair.write_le_into(&mut buf);
+	assert_eq!(buf, [1, 0xFE, 0xFF, 3, 4]);
+	assert_eq!(P::read_le_from(&buf), pair);
+
+	assert_eq!(Flags::WIRE_SIZE, 15);
+	let flags = Flags {
+		level: 5,
+		code: -3,
+		delay: Duration::new(1, 2),
+		pad: Unaligned::new(0x0708),
+	};
+	let mut buf = [0; 15];
+	flags.write_be_into(&mut buf);
+	assert_eq!(buf, [
+		0xED,
+		0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2,
+		7, 8,
+	]);
+	assert_eq!(Flags::read_be_from(&buf), flags);
+}
+
+#[test]
+fn padding() {
+	#[derive(Debug, Endian, PartialEq)]
+	#[endian(wire)]
+	struct Header {
+		tag: u8,
+		#[endian(align = 4)]
+		len: u32,
+		#[endian(pad_before = 2, reserved = 3)]
+		flags: u16,
+		#[endian(align = 8)]
+		end: u8,
+	}
+
+	assert_eq!(Header::WIRE_SIZE, 17);
+	let header = Header { tag: 1, len: 2, flags: 3, end: 4 };
+	let mut buf = [0xAA; 17];
+	header.write_le_into(&mut buf);
+	assert_eq!(buf, [
+		1, 0, 0, 0,
+		2, 0, 0, 0,
+		0, 0, 3, 0, 0, 0, 0, 0,
+		4,
+	]);
+	assert_eq!(Header::read_le_from(&buf), header);
+
+	//  Reading passes over padding and reserved bytes alike.
+	buf[1] = 0xFF;
+	buf[13] = 0xFF;
+	assert_eq!(Header::read_le_from(&buf), header);
+}
+
+#[derive(Debug, Endian, PartialEq)]
+#[endian(wire)]
+struct Datagram {
+	port: u16,
+	#[endian(checksum = internet)]
+	header_sum: u16,
+	kind: u8,
+	value: u16,
+	#[endian(checksum = crc16_ccitt)]
+	crc: u16,
+}
+
+#[test]
+fn checksums() {
+	use endian_trait::checksum;
+
+	assert_eq!(Datagram::WIRE_SIZE, 9);
+	let dgram = Datagram {
+		port: 0x1234,
+		header_sum: 0,
+		kind: 5,
+		value: 0x0607,
+		crc: 0,
+	};
+	let mut buf = [0; 9];
+	dgram.write_be_into(&mut buf);
+	assert_eq!(buf[.. 4], [0x12, 0x34, 0xED, 0xCB]);
+	assert_eq!(buf[4 .. 7], [5, 6, 7]);
+	assert_eq!(buf[7 ..], checksum::crc16_ccitt(&buf[.. 7]).to_be_bytes());
+
+	let back = Datagram::read_be_from(&buf);
+	assert_eq!(back.header_sum, 0xEDCB);
+	assert_eq!(back.crc, checksum::crc16_ccitt(&buf[.. 7]));
+}
+
+#[test]
+#[should_panic(expected = "`crc` does not match")]
+fn bad_checksum() {
+	let mut buf = [0; 9];
+	Datagram {
+		port: 1,
+		header_sum: 0,
+		kind: 2,
+		value: 0x0304,
+		crc: 0,
+	}.write_le_into(&mut buf);
+	buf[5] = 0;
+	Datagram::read_le_from(&buf);
+}
+
+#[test]
+#[should_panic(expected = "`version` does not hold its expected value")]
+fn bad_constant() {
+	#[derive(Debug, Endian, PartialEq)]
+	#[endian(wire)]
+	struct Header {
+		#[endian(magic = 0xFEED)]
+		magic: u16,
+		#[endian(const = 2)]
+		version: u8,
+	}
+
+	let mut buf = [0; 3];
+	Header { magic: 0, version: 0 }.write_be_into(&mut buf);
+	assert_eq!(buf, [0xFE, 0xED, 2]);
+	assert_eq!(Header::read_be_from(&buf), Header { magic: 0xFEED, version: 2 });
+	buf[2] = 3;
+	Header::read_be_from(&buf);
+}
+
+#[test]
+#[should_panic(expected = "not a discriminant of `Kind`")]
+fn bad_discriminant() {
+	Kind::read_be_from(&[0, 3]);
+}
+
+#[test]
+#[should_panic]
+fn short_buffer() {
+	get_record().write_be_into(&mut [0; 14]);
+}
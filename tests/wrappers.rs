@@ -0,0 +1,58 @@
+/*! Test `Endian` on cells and other single-value wrappers
+!*/
+
+extern crate endian_trait;
+
+use endian_trait::Endian;
+use std::cell::{
+	Cell,
+	UnsafeCell,
+};
+use std::cmp::Reverse;
+use std::mem::ManuallyDrop;
+use std::num::{
+	Saturating,
+	Wrapping,
+};
+
+#[derive(Debug, Endian)]
+struct Counters {
+	hits: Cell<u32>,
+	raw: UnsafeCell<u16>,
+	kept: ManuallyDrop<i32>,
+	rank: Reverse<u16>,
+	seq: Wrapping<u8>,
+	total: Saturating<u64>,
+}
+
+#[test]
+fn wrappers() {
+	let c = Counters {
+		hits: Cell::new(0x01020304),
+		raw: UnsafeCell::new(0x0506),
+		kept: ManuallyDrop::new(-2),
+		rank: Reverse(0x0708),
+		seq: Wrapping(9),
+		total: Saturating(0x0A0B),
+	};
+
+	let mut be = c.to_be();
+	assert_eq!(be.hits.get().to_ne_bytes(), [1, 2, 3, 4]);
+	assert_eq!(be.raw.get_mut().to_ne_bytes(), [5, 6]);
+	assert_eq!(be.kept.to_ne_bytes(), [0xFF, 0xFF, 0xFF, 0xFE]);
+	assert_eq!(be.rank.0.to_ne_bytes(), [7, 8]);
+	assert_eq!(be.seq.0, 9);
+	assert_eq!(be.total.0.to_ne_bytes(), [0, 0, 0, 0, 0, 0, 0x0A, 0x0B]);
+
+	let mut back = be.from_be();
+	assert_eq!(back.hits.get(), 0x01020304);
+	assert_eq!(*back.raw.get_mut(), 0x0506);
+	assert_eq!(*back.kept, -2);
+	assert_eq!(back.rank, Reverse(0x0708));
+	assert_eq!(back.total, Saturating(0x0A0B));
+
+	//  `Wrapping` has unstable inherent methods of the same names.
+	let le = Endian::to_le(Wrapping(0x0102u16));
+	assert_eq!(le.0.to_ne_bytes(), [2, 1]);
+	assert_eq!(Endian::from_le(le), Wrapping(0x0102));
+}
@@ -0,0 +1,58 @@
+/*! Test `Endian` on `zerocopy`'s fixed-order integer types
+!*/
+
+#![cfg(feature = "zerocopy")]
+
+extern crate endian_trait;
+extern crate zerocopy;
+
+use endian_trait::Endian;
+use endian_trait::bytes::AsBytes;
+use zerocopy::byteorder::{
+	BigEndian,
+	LittleEndian,
+	U16,
+	U32,
+};
+
+//  The `zerocopy` fields are already in a fixed order, and pass through the
+//  conversion untouched, while the primitive field is converted.
+#[repr(C)]
+#[derive(Clone, Copy, Endian)]
+#[endian(from_bytes, size = 12)]
+struct Mixed {
+	native: u32,
+	fixed_be: U32<BigEndian>,
+	fixed_le: U16<LittleEndian>,
+	tail: u16,
+}
+
+#[test]
+fn fixed_order_fields() {
+	let mixed = Mixed {
+		native: 0x0708090A,
+		fixed_be: U32::new(0x01020304),
+		fixed_le: U16::new(0x0506),
+		tail: 0x0B0C,
+	};
+
+	let be = mixed.to_be();
+	assert_eq!(be.as_bytes(), &[7, 8, 9, 10, 1, 2, 3, 4, 6, 5, 11, 12]);
+
+	let le = mixed.to_le();
+	assert_eq!(le.as_bytes(), &[10, 9, 8, 7, 1, 2, 3, 4, 6, 5, 12, 11]);
+
+	let back = be.from_be();
+	assert_eq!(back.native, 0x0708090A);
+	assert_eq!(back.fixed_be.get(), 0x01020304);
+	assert_eq!(back.fixed_le.get(), 0x0506);
+	assert_eq!(back.tail, 0x0B0C);
+}
+
+#[test]
+fn read_converted() {
+	let raw = [1, 2, 3, 4];
+	assert_eq!(endian_trait::zerocopy::read_be::<u32>(&raw), Ok(0x01020304));
+	assert_eq!(endian_trait::zerocopy::read_le::<u32>(&raw), Ok(0x04030201));
+	assert!(endian_trait::zerocopy::read_be::<u64>(&raw).is_err());
+}